@@ -1,31 +1,38 @@
-use crate::schema::{Activity, Agent, DaemonStatus, Spawn, TailEntry};
+use crate::config::{Credential, Instance};
+use crate::schema::{Activity, Agent, DaemonStatus, LedgerQuery, Spawn, TailEntry};
+use crate::source::Endpoint;
+use crate::wire::{
+    self, AgentPayload, LedgerPayload, SpawnEventPayload, SpawnEventsPayload, SpawnPayload,
+};
+use reqwest::StatusCode;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
 use std::collections::HashMap;
 use std::env;
-use std::sync::OnceLock;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 const DEFAULT_BASE: &str = "http://localhost:8228";
-const TIMEOUT: Duration = Duration::from_millis(800);
+pub const TIMEOUT: Duration = Duration::from_millis(800);
+const IDENTITY_HEADER: &str = "SPACE_IDENTITY";
+const API_KEY_HEADER: &str = "x-api-key";
 
 pub fn api_base_url() -> String {
     env::var("SPACE_API_URL").unwrap_or_else(|_| DEFAULT_BASE.to_string())
 }
 
-static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
-
-fn client() -> &'static reqwest::Client {
-    CLIENT.get_or_init(|| {
-        reqwest::Client::builder()
-            .timeout(TIMEOUT)
-            .build()
-            .expect("failed to create http client")
-    })
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ApiError {
     Network(String),
     Decode(String),
+    Http(u16, String),
+    // 401/403: the credential is missing, wrong or lacks access.
+    Unauthorized(u16),
+}
+
+impl ApiError {
+    pub fn is_auth(&self) -> bool {
+        matches!(self, ApiError::Unauthorized(_))
+    }
 }
 
 impl std::fmt::Display for ApiError {
@@ -33,6 +40,11 @@ impl std::fmt::Display for ApiError {
         match self {
             ApiError::Network(e) => write!(f, "network: {}", e),
             ApiError::Decode(e) => write!(f, "decode: {}", e),
+            ApiError::Http(status, e) => write!(f, "http {}: {}", status, e),
+            ApiError::Unauthorized(401) => write!(f, "unauthorized (401): check the token"),
+            ApiError::Unauthorized(status) => {
+                write!(f, "forbidden ({}): token lacks access", status)
+            }
         }
     }
 }
@@ -41,171 +53,317 @@ impl std::error::Error for ApiError {}
 
 type Result<T> = std::result::Result<T, ApiError>;
 
-async fn get_json<T: serde::de::DeserializeOwned>(path: &str) -> Result<T> {
-    let url = format!("{}{}", api_base_url(), path);
-    let response = client()
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| ApiError::Network(e.to_string()))?;
-
-    response
-        .json::<T>()
-        .await
-        .map_err(|e| ApiError::Decode(e.to_string()))
+fn check_auth(status: StatusCode) -> Result<()> {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(ApiError::Unauthorized(status.as_u16()))
+        }
+        _ => Ok(()),
+    }
 }
 
-pub async fn get_health() -> Result<serde_json::Value> {
-    get_json("/api/health").await
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    base_url: String,
+    http: reqwest::Client,
+    // Marked sensitive so the token never appears in Debug output.
+    auth: HeaderMap,
+    identity: Option<String>,
+    ca_pem: Option<Vec<u8>>,
+    // Rows dropped from the latest response of each endpoint, until `take_rejected`.
+    rejected: Arc<Mutex<HashMap<Endpoint, Vec<String>>>>,
 }
 
-pub async fn health_ok() -> bool {
-    get_health()
-        .await
-        .map(|v| v["database"]["connected"].as_bool().unwrap_or(false))
-        .unwrap_or(false)
-}
+impl ApiClient {
+    pub fn new(base_url: impl Into<String>) -> std::io::Result<Self> {
+        Self::connect(&Instance::new("", base_url))
+    }
 
-pub async fn get_agents() -> Result<Vec<Agent>> {
-    let raw: Vec<serde_json::Value> = get_json("/api/agents").await?;
-    Ok(raw
-        .into_iter()
-        .map(|v| Agent {
-            id: v["id"].as_str().unwrap_or("").to_string(),
-            identity: v["identity"].as_str().unwrap_or("").to_string(),
-            agent_type: v["type"].as_str().unwrap_or("ai").to_string(),
-            model: v["model"].as_str().map(String::from),
-            constitution: v["constitution"].as_str().map(String::from),
-            avatar_path: v["avatar_path"].as_str().map(String::from),
-            color: v["color"].as_str().map(String::from),
-            created_at: v["created_at"].as_str().unwrap_or("").to_string(),
-            archived_at: v["archived_at"].as_str().map(String::from),
-        })
-        .collect())
-}
+    // Fails when the CA file can't be read or the token isn't a valid header value.
+    pub fn connect(instance: &Instance) -> std::io::Result<Self> {
+        let mut auth = HeaderMap::new();
+        if let Some(credential) = instance.credential() {
+            let (name, value) = match &credential {
+                Credential::Bearer(token) => (AUTHORIZATION, format!("Bearer {}", token.expose())),
+                Credential::ApiKey(key) => (
+                    HeaderName::from_static(API_KEY_HEADER),
+                    key.expose().to_string(),
+                ),
+            };
+            let mut value = HeaderValue::from_str(&value).map_err(|_| {
+                std::io::Error::other(format!(
+                    "{}: token is not a valid header value",
+                    instance.name
+                ))
+            })?;
+            value.set_sensitive(true);
+            auth.insert(name, value);
+        }
 
-pub async fn get_agent_identities() -> Result<HashMap<String, String>> {
-    let agents = get_agents().await?;
-    Ok(agents.into_iter().map(|a| (a.id, a.identity)).collect())
-}
+        let timeout = instance
+            .timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(TIMEOUT);
+        let mut builder = reqwest::Client::builder()
+            .timeout(timeout)
+            .default_headers(auth.clone());
+        let ca_pem = match &instance.ca_cert {
+            Some(path) => Some(
+                std::fs::read(path)
+                    .map_err(|e| std::io::Error::other(format!("{}: {}", path.display(), e)))?,
+            ),
+            None => None,
+        };
+        if let Some(pem) = &ca_pem {
+            for cert in reqwest::Certificate::from_pem_bundle(pem).map_err(std::io::Error::other)? {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
 
-pub async fn get_spawns() -> Result<Vec<Spawn>> {
-    let raw: Vec<serde_json::Value> = get_json("/api/spawns").await?;
-    Ok(raw
-        .into_iter()
-        .map(|v| Spawn {
-            id: v["id"].as_str().unwrap_or("").to_string(),
-            agent_id: v["agent_id"].as_str().unwrap_or("").to_string(),
-            project_id: v["project_id"].as_str().map(String::from),
-            caller_spawn_id: v["caller_spawn_id"].as_str().map(String::from),
-            source: v["source"].as_str().map(String::from),
-            status: v["status"].as_str().unwrap_or("done").to_string(),
-            error: v["error"].as_str().map(String::from),
-            pid: v["pid"].as_i64().map(|p| p as i32),
-            session_id: v["session_id"].as_str().map(String::from),
-            summary: v["summary"].as_str().map(String::from),
-            trace_hash: v["trace_hash"].as_str().map(String::from),
-            created_at: v["created_at"].as_str().unwrap_or("").to_string(),
-            last_active_at: v["last_active_at"].as_str().map(String::from),
+        Ok(Self {
+            base_url: instance.url.clone(),
+            http: builder.build().map_err(std::io::Error::other)?,
+            auth,
+            identity: instance.identity.clone(),
+            ca_pem,
+            rejected: Arc::default(),
         })
-        .collect())
-}
+    }
 
-pub async fn get_activity(limit: usize) -> Result<Vec<Activity>> {
-    let raw: Vec<serde_json::Value> = get_json(&format!("/api/ledger?limit={}", limit)).await?;
-    Ok(raw.into_iter().filter_map(ledger_to_activity).collect())
-}
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
 
-pub async fn get_agent_activity(agent_id: &str, limit: usize) -> Result<Vec<Activity>> {
-    let all = get_activity(limit * 2).await?;
-    Ok(all
-        .into_iter()
-        .filter(|a| a.agent_id == agent_id)
-        .take(limit)
-        .collect())
-}
+    pub fn identity(&self) -> Option<&str> {
+        self.identity.as_deref()
+    }
 
-pub async fn get_ledger_activity(limit: usize) -> Result<Vec<Activity>> {
-    get_activity(limit).await
-}
+    // Credential headers, for connections reqwest doesn't make (the WS).
+    pub fn auth_headers(&self) -> &HeaderMap {
+        &self.auth
+    }
 
-pub async fn get_spawn_activity(spawn_id: &str, _limit: usize) -> Result<Vec<Activity>> {
-    let events: serde_json::Value =
-        get_json(&format!("/api/spawns/{}/events?limit=200", spawn_id)).await?;
-    let items = events["events"].as_array().cloned().unwrap_or_default();
-    Ok(items
-        .into_iter()
-        .enumerate()
-        .map(|(i, v)| Activity {
-            id: i as i64,
-            agent_id: v["agent_id"].as_str().unwrap_or("").to_string(),
-            spawn_id: Some(spawn_id.to_string()),
-            primitive: v["type"].as_str().unwrap_or("event").to_string(),
-            primitive_id: spawn_id.to_string(),
-            action: v["type"].as_str().unwrap_or("").to_string(),
-            field: v["name"].as_str().map(String::from),
-            after: v["content"]
-                .as_str()
-                .map(String::from)
-                .or_else(|| v["args"].as_str().map(String::from)),
-            created_at: v["timestamp"].as_str().unwrap_or("").to_string(),
-        })
-        .collect())
-}
+    pub fn ca_pem(&self) -> Option<&[u8]> {
+        self.ca_pem.as_deref()
+    }
 
-fn ledger_to_activity(v: serde_json::Value) -> Option<Activity> {
-    Some(Activity {
-        id: 0,
-        agent_id: v["agent_id"].as_str()?.to_string(),
-        spawn_id: None,
-        primitive: v["type"].as_str()?.to_string(),
-        primitive_id: v["id"].as_str()?.to_string(),
-        action: v["status"].as_str().unwrap_or("created").to_string(),
-        field: None,
-        after: v["content"].as_str().map(String::from),
-        created_at: v["created_at"].as_str()?.to_string(),
-    })
-}
+    pub fn take_rejected(&self, endpoint: Endpoint) -> Vec<String> {
+        let mut rejected = self.rejected.lock().unwrap_or_else(|e| e.into_inner());
+        rejected.remove(&endpoint).unwrap_or_default()
+    }
+
+    fn keep_rows<P: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: Endpoint,
+        values: Vec<serde_json::Value>,
+    ) -> Vec<P> {
+        self.keep_indexed_rows(endpoint, values)
+            .into_iter()
+            .map(|(_, row)| row)
+            .collect()
+    }
+
+    fn keep_indexed_rows<P: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: Endpoint,
+        values: Vec<serde_json::Value>,
+    ) -> Vec<(usize, P)> {
+        let (rows, dropped) = wire::decode_indexed_rows(values);
+        let mut rejected = self.rejected.lock().unwrap_or_else(|e| e.into_inner());
+        rejected.insert(endpoint, dropped);
+        rows
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.get_json_with(path, &[]).await
+    }
+
+    async fn get_json_with<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self
+            .http
+            .get(&url)
+            .query(params)
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+        let status = response.status();
+        check_auth(status)?;
+        let text = response
+            .text()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+        // A proxy error page is an outage, not schema drift.
+        if !status.is_success() {
+            return Err(ApiError::Http(status.as_u16(), text));
+        }
 
-pub async fn get_daemon_status(_active_count: usize) -> DaemonStatus {
-    get_json::<DaemonStatus>("/api/swarm/daemon")
+        serde_json::from_str(&text).map_err(|e| ApiError::Decode(e.to_string()))
+    }
+
+    async fn send_json(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        self.send_json_as(method, path, body, self.identity.as_deref())
+            .await
+    }
+
+    async fn send_json_as(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: serde_json::Value,
+        identity: Option<&str>,
+    ) -> Result<serde_json::Value> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut request = self.http.request(method, &url).json(&body);
+        if let Some(identity) = identity {
+            request = request.header(IDENTITY_HEADER, identity);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        let status = response.status();
+        check_auth(status)?;
+        let text = response
+            .text()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+        if !status.is_success() {
+            return Err(ApiError::Http(status.as_u16(), text));
+        }
+        if text.trim().is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+        serde_json::from_str(&text).map_err(|e| ApiError::Decode(e.to_string()))
+    }
+
+    pub async fn get_health(&self) -> Result<serde_json::Value> {
+        self.get_json("/api/health").await
+    }
+
+    // Untyped fetch for contract checks.
+    pub async fn get_value(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<serde_json::Value> {
+        self.get_json_with(path, params).await
+    }
+
+    pub async fn get_agents(&self) -> Result<Vec<Agent>> {
+        let raw = self.get_json("/api/agents").await?;
+        let rows: Vec<AgentPayload> = self.keep_rows(Endpoint::Agents, raw);
+        Ok(rows.into_iter().map(Agent::from).collect())
+    }
+
+    pub async fn get_spawns(&self) -> Result<Vec<Spawn>> {
+        let raw = self.get_json("/api/spawns").await?;
+        let rows: Vec<SpawnPayload> = self.keep_rows(Endpoint::Spawns, raw);
+        Ok(rows.into_iter().map(Spawn::from).collect())
+    }
+
+    pub async fn get_ledger(&self, query: &LedgerQuery) -> Result<Vec<Activity>> {
+        let raw = self.get_json_with("/api/ledger", &query.params()).await?;
+        let rows: Vec<LedgerPayload> = self.keep_rows(Endpoint::Ledger, raw);
+        // A server that ignores the filters would otherwise flood a single agent's pane.
+        Ok(rows
+            .into_iter()
+            .map(Activity::from)
+            .filter(|act| query.matches(act))
+            .collect())
+    }
+
+    pub async fn get_spawn_activity(&self, spawn_id: &str, limit: usize) -> Result<Vec<Activity>> {
+        let raw: SpawnEventsPayload = self
+            .get_json(&format!("/api/spawns/{}/events?limit={}", spawn_id, limit))
+            .await?;
+        // Indices count dropped rows too, so a bad row doesn't shift the others' ids.
+        let rows: Vec<(usize, SpawnEventPayload)> =
+            self.keep_indexed_rows(Endpoint::SpawnEvents, raw.events);
+        Ok(rows
+            .into_iter()
+            .map(|(i, event)| event.into_activity(i, spawn_id))
+            .collect())
+    }
+
+    pub async fn get_daemon_status(&self) -> Result<DaemonStatus> {
+        self.get_json::<DaemonStatus>("/api/swarm/daemon").await
+    }
+
+    pub async fn get_tail(&self, limit: usize) -> Result<Vec<TailEntry>> {
+        self.get_json::<Vec<TailEntry>>(&format!("/api/swarm/tail?limit={}", limit))
+            .await
+    }
+
+    pub async fn get_agent_tail(&self, agent: &str, limit: usize) -> Result<Vec<TailEntry>> {
+        self.get_json::<Vec<TailEntry>>(&format!("/api/swarm/tail?limit={}&agent={}", limit, agent))
+            .await
+    }
+
+    // Tasks are attributed to `creator_id` rather than the configured identity.
+    pub async fn create_task(&self, content: &str, creator_id: &str) -> Result<serde_json::Value> {
+        self.send_json_as(
+            reqwest::Method::POST,
+            "/api/tasks",
+            serde_json::json!({ "content": content }),
+            Some(creator_id),
+        )
         .await
-        .unwrap_or_default()
-}
+    }
 
-pub async fn get_tail(limit: usize) -> Vec<TailEntry> {
-    get_json::<Vec<TailEntry>>(&format!("/api/swarm/tail?limit={}", limit))
+    pub async fn kill_spawn(&self, spawn_id: &str) -> Result<serde_json::Value> {
+        self.send_json(
+            reqwest::Method::POST,
+            &format!("/api/spawns/{}/kill", spawn_id),
+            serde_json::json!({}),
+        )
         .await
-        .unwrap_or_default()
-}
+    }
 
-pub async fn get_agent_tail(agent: &str, limit: usize) -> Vec<TailEntry> {
-    get_json::<Vec<TailEntry>>(&format!("/api/swarm/tail?limit={}&agent={}", limit, agent))
+    pub async fn retry_spawn(&self, spawn_id: &str) -> Result<serde_json::Value> {
+        self.send_json(
+            reqwest::Method::POST,
+            &format!("/api/spawns/{}/retry", spawn_id),
+            serde_json::json!({}),
+        )
         .await
-        .unwrap_or_default()
-}
+    }
 
-pub async fn get_human_agent() -> Result<Option<Agent>> {
-    let agents = get_agents().await?;
-    Ok(agents.into_iter().find(|a| a.agent_type == "human"))
-}
+    pub async fn set_swarm_enabled(&self, enabled: bool) -> Result<serde_json::Value> {
+        self.send_json(
+            reqwest::Method::PATCH,
+            "/api/swarm/daemon",
+            serde_json::json!({ "enabled": enabled }),
+        )
+        .await
+    }
 
-pub async fn create_task(content: &str, creator_id: &str) -> Result<serde_json::Value> {
-    let url = format!("{}{}", api_base_url(), "/api/tasks");
-    let body = serde_json::json!({
-        "content": content,
-    });
-
-    let response = client()
-        .post(&url)
-        .header("SPACE_IDENTITY", creator_id)
-        .json(&body)
-        .send()
+    pub async fn set_daemon_running(&self, running: bool) -> Result<serde_json::Value> {
+        let action = if running { "start" } else { "stop" };
+        self.send_json(
+            reqwest::Method::POST,
+            &format!("/api/swarm/daemon/{}", action),
+            serde_json::json!({}),
+        )
         .await
-        .map_err(|e| ApiError::Network(e.to_string()))?;
+    }
 
-    response
-        .json()
+    pub async fn set_swarm_concurrency(&self, concurrency: u32) -> Result<serde_json::Value> {
+        self.send_json(
+            reqwest::Method::PATCH,
+            "/api/swarm/daemon",
+            serde_json::json!({ "concurrency": concurrency }),
+        )
         .await
-        .map_err(|e| ApiError::Decode(e.to_string()))
+    }
 }
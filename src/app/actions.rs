@@ -0,0 +1,78 @@
+use super::{AppState, Confirm, ToastKind};
+use crate::command::Command;
+use crate::schema::Spawn;
+
+fn short(id: &str) -> &str {
+    &id[..id.len().min(8)]
+}
+
+impl AppState {
+    pub fn focused_spawn(&self) -> Option<&Spawn> {
+        self.spawns.get(self.active_spawn_idx)
+    }
+
+    pub fn request_kill(&mut self) {
+        if let Some(id) = self.focused_spawn().map(|s| s.id.clone()) {
+            self.request_kill_spawn(&id);
+        }
+    }
+
+    // `:kill` and the `x` key share the same confirmation.
+    pub fn request_kill_spawn(&mut self, spawn_id: &str) {
+        let Some(spawn) = self.spawns.iter().find(|s| s.id == spawn_id) else {
+            return;
+        };
+        if spawn.status != "active" {
+            let message = format!("spawn {} is not active", short(&spawn.id));
+            self.show_toast(ToastKind::Error, message);
+            return;
+        }
+
+        let pid = spawn
+            .pid
+            .map(|p| format!(", pid {}", p))
+            .unwrap_or_default();
+        let prompt = format!(
+            "Kill spawn {} ({}{})? [y/N]",
+            short(&spawn.id),
+            self.resolve_identity(&spawn.agent_id),
+            pid
+        );
+        self.confirm = Some(Confirm {
+            prompt,
+            command: Command::Kill(spawn.id.clone()),
+        });
+    }
+
+    pub fn request_retry(&mut self) -> Option<Command> {
+        let spawn = self.focused_spawn()?;
+        if !spawn.is_failed() {
+            let message = format!("spawn {} has not failed", short(&spawn.id));
+            self.show_toast(ToastKind::Error, message);
+            return None;
+        }
+        Some(Command::Retry(spawn.id.clone()))
+    }
+
+    pub fn toggle_follow_up(&mut self) {
+        let Some(id) = self.focused_spawn().map(|s| s.id.clone()) else {
+            return;
+        };
+        let message = if self.flagged_spawns.remove(&id) {
+            format!("unflagged {}", short(&id))
+        } else {
+            let message = format!("flagged {} for follow-up", short(&id));
+            self.flagged_spawns.insert(id);
+            message
+        };
+        self.show_toast(ToastKind::Success, message);
+    }
+
+    pub fn take_confirmed(&mut self) -> Option<Command> {
+        self.confirm.take().map(|c| c.command)
+    }
+
+    pub fn cancel_confirm(&mut self) {
+        self.confirm = None;
+    }
+}
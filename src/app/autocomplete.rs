@@ -1,65 +1,263 @@
-use super::{AppState, AutocompleteMode};
+use super::{AppState, AutocompleteMode, Hint};
+use crate::command::{self, ArgKind};
+use crate::fuzzy::{self, Match};
+use crate::schema::{Agent, Spawn};
+use std::cmp::Reverse;
+use std::path::PathBuf;
+
+// Directory listings are cut off here so a huge directory can't stall typing.
+const MAX_DIR_ENTRIES: usize = 500;
+
+// `t/`, `d/` and `i/` complete ledger items of that primitive.
+fn ledger_primitive(word: &str) -> Option<&'static str> {
+    match word.get(..2)? {
+        "t/" => Some("task"),
+        "d/" => Some("decision"),
+        "i/" => Some("insight"),
+        _ => None,
+    }
+}
+
+// Any word with a slash, relative or absolute; ledger triggers are matched first.
+fn is_path(word: &str) -> bool {
+    word.contains('/') && word != "/"
+}
+
+fn short_id(id: &str) -> String {
+    id[..id.len().min(8)].to_string()
+}
+
+// The text kept in front of the chosen value: the trigger, or a path's directory part.
+fn completion_base(mode: AutocompleteMode, word: &str) -> &str {
+    match mode {
+        AutocompleteMode::Agent => "@",
+        AutocompleteMode::Spawn => "#",
+        AutocompleteMode::Command => ":",
+        AutocompleteMode::Argument => "",
+        AutocompleteMode::File | AutocompleteMode::Ledger => {
+            &word[..word.rfind('/').map_or(0, |i| i + 1)]
+        }
+    }
+}
 
 impl AppState {
+    // Completion works on the word that ends at the cursor.
     fn last_word_start(&self) -> usize {
-        self.input_text
+        self.input_text[..self.cursor()]
             .rfind(|c: char| c.is_whitespace())
             .map(|i| i + 1)
             .unwrap_or(0)
     }
 
+    pub fn is_completing(&self) -> bool {
+        self.autocomplete_mode.is_some() && !self.autocomplete_list.is_empty()
+    }
+
     pub fn detect_and_trigger_autocomplete(&mut self) {
         if self.input_text.is_empty() {
             return;
         }
+        if self.is_command_mode() {
+            self.detect_command_autocomplete();
+            return;
+        }
+
+        let word = self.input_text[self.last_word_start()..self.cursor()].to_string();
+        self.cancel_autocomplete();
+        let (mode, query) = if let Some(query) = word.strip_prefix('@') {
+            self.load_agent_autocomplete();
+            (AutocompleteMode::Agent, query)
+        } else if let Some(query) = word.strip_prefix('#') {
+            self.load_spawn_autocomplete(|_| true);
+            (AutocompleteMode::Spawn, query)
+        } else if let Some(primitive) = ledger_primitive(&word) {
+            self.load_ledger_autocomplete(primitive);
+            (AutocompleteMode::Ledger, &word[2..])
+        } else if is_path(&word) {
+            self.load_file_autocomplete(&word);
+            let query = &word[word.rfind('/').map_or(0, |i| i + 1)..];
+            (AutocompleteMode::File, query)
+        } else {
+            return;
+        };
+
+        self.autocomplete_mode = Some(mode);
+        self.autocomplete_query = query.to_string();
+        self.filter_autocomplete();
+    }
+
+    fn detect_command_autocomplete(&mut self) {
+        let Some((name, arg)) = command::split(&self.input_text) else {
+            return;
+        };
+        let name = name.to_string();
+        let arg = arg.to_string();
+        let typing_name = !self.input_text.trim_start()[1..].contains(char::is_whitespace);
+        self.cancel_autocomplete();
+
+        let (mode, query) = if typing_name {
+            for c in command::COMMANDS {
+                self.offer(c.name.to_string(), "", c.summary.to_string(), false);
+            }
+            (AutocompleteMode::Command, name)
+        } else {
+            match command::spec(&name).map(|c| c.arg) {
+                _ if arg.contains(char::is_whitespace) => {}
+                Some(ArgKind::Agent) => self.load_agent_autocomplete(),
+                Some(ArgKind::Spawn) => self.load_spawn_autocomplete(|s| s.status == "active"),
+                Some(ArgKind::FailedSpawn) => self.load_spawn_autocomplete(Spawn::is_failed),
+                Some(ArgKind::Choice(options)) => {
+                    for option in options {
+                        self.offer(option.to_string(), "", String::new(), false);
+                    }
+                }
+                _ => {}
+            }
+            (AutocompleteMode::Argument, arg)
+        };
+
+        self.autocomplete_mode = Some(mode);
+        self.autocomplete_query = query;
+        self.filter_autocomplete();
+        if self.autocomplete_list.is_empty() {
+            self.cancel_autocomplete();
+        }
+    }
+
+    fn offer(&mut self, value: String, icon: &'static str, detail: String, searchable: bool) {
+        if self.autocomplete_hints.contains_key(&value) {
+            return;
+        }
+        self.autocomplete_hints.insert(
+            value.clone(),
+            Hint {
+                icon,
+                detail,
+                searchable,
+            },
+        );
+        self.autocomplete_list.push(value);
+    }
 
-        let last_word_start = self.last_word_start();
-        let last_word = &self.input_text[last_word_start..];
-
-        if let Some(query) = last_word.strip_prefix('@') {
-            self.autocomplete_mode = Some(AutocompleteMode::Agent);
-            self.autocomplete_query = query.to_string();
-        } else if let Some(query) = last_word.strip_prefix('/')
-            && !query.is_empty()
-        {
-            self.autocomplete_mode = Some(AutocompleteMode::File);
-            self.autocomplete_query = query.to_string();
-            self.load_file_autocomplete();
+    fn agent_hint(&self, agent: &Agent) -> String {
+        let active = self
+            .spawns
+            .iter()
+            .filter(|s| s.agent_id == agent.id && s.status == "active")
+            .count();
+        match active {
+            0 => String::new(),
+            n => format!("{} active", n),
         }
     }
 
-    pub async fn load_agent_autocomplete(&mut self) {
-        if let Ok(agents) = crate::api::get_agents().await {
-            self.autocomplete_list = agents.iter().map(|a| a.identity.clone()).collect();
-            self.filter_autocomplete();
+    pub fn load_agent_autocomplete(&mut self) {
+        let agents: Vec<(String, &'static str, String)> = self
+            .agents
+            .iter()
+            .map(|a| (a.identity.clone(), a.type_icon(), self.agent_hint(a)))
+            .collect();
+        for (identity, icon, detail) in agents {
+            self.offer(identity, icon, detail, false);
         }
     }
 
-    pub fn load_file_autocomplete(&mut self) {
-        use std::fs;
+    // Matches the spawn's agent and status as well as its id.
+    pub fn load_spawn_autocomplete(&mut self, keep: impl Fn(&Spawn) -> bool) {
+        let spawns: Vec<(String, &'static str, String)> = self
+            .spawns
+            .iter()
+            .filter(|s| keep(s))
+            .map(|s| {
+                let detail = format!("{} {}", self.resolve_identity(&s.agent_id), s.status);
+                (short_id(&s.id), s.status_icon(), detail)
+            })
+            .collect();
+        for (id, icon, detail) in spawns {
+            self.offer(id, icon, detail, true);
+        }
+    }
 
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let space_dir = format!("{}/.space", home);
+    // Ledger items are picked by their text and inserted by id.
+    pub fn load_ledger_autocomplete(&mut self, primitive: &str) {
+        let items: Vec<(String, String)> = self
+            .ledger
+            .iter()
+            .filter(|item| item.primitive == primitive)
+            .map(|item| {
+                let text = item.after.as_deref().unwrap_or_default();
+                let line = text.lines().next().unwrap_or_default();
+                (
+                    short_id(&item.primitive_id),
+                    line.chars().take(60).collect(),
+                )
+            })
+            .collect();
+        for (id, text) in items {
+            self.offer(id, "", text, true);
+        }
+    }
 
-        if let Ok(entries) = fs::read_dir(&space_dir) {
-            let mut files = Vec::new();
-            for entry in entries.flatten() {
-                if let Ok(metadata) = entry.metadata()
-                    && let Some(name) = entry.path().file_name().and_then(|n| n.to_str())
-                    && metadata.is_file()
-                {
-                    files.push(name.to_string());
-                }
+    // Lists the directory a path word points into: `~/` is home, `./` and `../`
+    // are relative to the working directory. Directories sort first and end in `/`.
+    pub fn load_file_autocomplete(&mut self, word: &str) {
+        let dir = &word[..word.rfind('/').map_or(0, |i| i + 1)];
+        let dir = match dir.strip_prefix("~/") {
+            Some(rest) => match std::env::var("HOME") {
+                Ok(home) => PathBuf::from(home).join(rest),
+                Err(_) => return,
+            },
+            None => PathBuf::from(dir),
+        };
+        let hidden = word[word.rfind('/').map_or(0, |i| i + 1)..].starts_with('.');
+
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return;
+        };
+        let mut names: Vec<(bool, String)> = entries
+            .flatten()
+            .take(MAX_DIR_ENTRIES)
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.to_string();
+                let is_dir = entry.file_type().ok()?.is_dir()
+                    || entry.path().metadata().is_ok_and(|m| m.is_dir());
+                (hidden || !name.starts_with('.')).then_some((!is_dir, name))
+            })
+            .collect();
+        names.sort();
+        for (is_file, name) in names {
+            match is_file {
+                true => self.offer(name, "", String::new(), false),
+                false => self.offer(format!("{}/", name), "▸", String::new(), false),
             }
-            self.autocomplete_list = files;
-            self.filter_autocomplete();
         }
     }
 
+    // Where the query matched: in the value, or (for searchable hints) in the detail.
+    pub fn autocomplete_match(&self, item: &str) -> Option<(Match, bool)> {
+        let query = &self.autocomplete_query;
+        let in_value = fuzzy::find(query, item);
+        let in_detail = self
+            .autocomplete_hints
+            .get(item)
+            .filter(|h| h.searchable)
+            .and_then(|h| fuzzy::find(query, &h.detail));
+        match (in_value, in_detail) {
+            (Some(v), Some(d)) if d.score > v.score => Some((d, true)),
+            (Some(v), _) => Some((v, false)),
+            (None, Some(d)) => Some((d, true)),
+            (None, None) => None,
+        }
+    }
+
+    // Fuzzy filter, best match first; equal scores keep the source order.
     pub fn filter_autocomplete(&mut self) {
-        let query = self.autocomplete_query.to_lowercase();
-        self.autocomplete_list
-            .retain(|item| item.to_lowercase().contains(&query));
+        let mut ranked: Vec<(i32, String)> = std::mem::take(&mut self.autocomplete_list)
+            .into_iter()
+            .filter_map(|item| Some((self.autocomplete_match(&item)?.0.score, item)))
+            .collect();
+        ranked.sort_by_key(|(score, _)| Reverse(*score));
+        self.autocomplete_list = ranked.into_iter().map(|(_, item)| item).collect();
         self.autocomplete_idx = 0;
     }
 
@@ -79,28 +277,27 @@ impl AppState {
         }
     }
 
+    // A directory completes without the trailing space and lists its contents next.
     pub fn autocomplete_select(&mut self) {
         if self.autocomplete_list.is_empty() {
             return;
         }
-
-        let selection = self.autocomplete_list[self.autocomplete_idx].clone();
-        let trigger = match self.autocomplete_mode {
-            Some(AutocompleteMode::Agent) => "@",
-            Some(AutocompleteMode::File) => "/",
-            None => return,
+        let Some(mode) = self.autocomplete_mode else {
+            return;
         };
 
-        let start = self.last_word_start();
-        self.input_text.truncate(start);
-        self.input_text.push_str(trigger);
-        self.input_text.push_str(&selection);
-        self.input_text.push(' ');
+        let selection = self.autocomplete_list[self.autocomplete_idx].clone();
+        let (start, end) = (self.last_word_start(), self.cursor());
+        let base = completion_base(mode, &self.input_text[start..end]);
+        let space = if selection.ends_with('/') { "" } else { " " };
+        let completed = format!("{}{}{}", base, selection, space);
+        self.input_text.replace_range(start..end, &completed);
+        self.set_cursor(start + completed.len());
 
-        self.autocomplete_mode = None;
-        self.autocomplete_list.clear();
-        self.autocomplete_idx = 0;
-        self.autocomplete_query.clear();
+        self.cancel_autocomplete();
+        if mode == AutocompleteMode::File && space.is_empty() {
+            self.detect_and_trigger_autocomplete();
+        }
     }
 
     pub fn cancel_autocomplete(&mut self) {
@@ -108,5 +305,6 @@ impl AppState {
         self.autocomplete_list.clear();
         self.autocomplete_idx = 0;
         self.autocomplete_query.clear();
+        self.autocomplete_hints.clear();
     }
 }
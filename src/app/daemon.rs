@@ -0,0 +1,119 @@
+use super::{AppState, Confirm, DaemonChange, PendingDaemon, ToastKind};
+use crate::bridge::Outcome;
+use crate::command::Command;
+use crate::schema::DaemonStatus;
+use std::time::{Duration, Instant};
+
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn change_for(cmd: &Command) -> Option<DaemonChange> {
+    match cmd {
+        Command::Swarm(enabled) => Some(DaemonChange::Enabled(*enabled)),
+        Command::Daemon(running) => Some(DaemonChange::Running(*running)),
+        Command::Concurrency(n) => Some(DaemonChange::Concurrency(*n as i32)),
+        _ => None,
+    }
+}
+
+fn is_applied(change: DaemonChange, status: &DaemonStatus) -> bool {
+    match change {
+        DaemonChange::Enabled(enabled) => status.enabled == enabled,
+        DaemonChange::Running(running) => status.running == running,
+        DaemonChange::Concurrency(n) => status.concurrency == n,
+    }
+}
+
+impl AppState {
+    fn target_concurrency(&self) -> i32 {
+        match self.daemon_pending.as_ref().map(|p| p.change) {
+            Some(DaemonChange::Concurrency(n)) => n,
+            _ => self.daemon.concurrency,
+        }
+    }
+
+    pub fn swarm_toggle_command(&self) -> Command {
+        let enabled = match self.daemon_pending.as_ref().map(|p| p.change) {
+            Some(DaemonChange::Enabled(enabled)) => enabled,
+            _ => self.daemon.enabled,
+        };
+        Command::Swarm(!enabled)
+    }
+
+    pub fn concurrency_command(&self, delta: i32) -> Option<Command> {
+        let current = self.target_concurrency();
+        let next = (current + delta).max(1);
+        (next != current).then_some(Command::Concurrency(next as u32))
+    }
+
+    pub fn request_daemon_toggle(&mut self) -> Option<Command> {
+        // Wait for the previous change to show up in `daemon` first.
+        if self.daemon_pending.is_some() {
+            self.show_toast(ToastKind::Error, "daemon change still pending");
+            return None;
+        }
+        if !self.daemon.running {
+            return Some(Command::Daemon(true));
+        }
+        self.confirm = Some(Confirm {
+            prompt: format!(
+                "Stop swarm daemon ({} active)? [y/N]",
+                self.daemon.active_count
+            ),
+            command: Command::Daemon(false),
+        });
+        None
+    }
+
+    pub fn begin_daemon_change(&mut self, cmd: &Command) {
+        if let Some(change) = change_for(cmd) {
+            self.daemon_pending = Some(PendingDaemon {
+                change,
+                since: Instant::now(),
+            });
+        }
+    }
+
+    pub fn finish_command(&mut self, cmd: Option<&Command>, outcome: Outcome) {
+        let change = cmd.and_then(change_for);
+        // Confirm from a fresh status rather than waiting on a WS event.
+        if change.is_some() {
+            self.daemon_checks += 1;
+        }
+        if !outcome.ok
+            && change.is_some()
+            && self.daemon_pending.as_ref().map(|p| p.change) == change
+        {
+            self.daemon_pending = None;
+        }
+        let kind = if outcome.ok {
+            ToastKind::Success
+        } else {
+            ToastKind::Error
+        };
+        self.show_toast(kind, outcome.message);
+    }
+
+    pub fn set_daemon(&mut self, status: DaemonStatus) {
+        self.daemon = status;
+        if self
+            .daemon_pending
+            .as_ref()
+            .is_some_and(|p| is_applied(p.change, &self.daemon))
+        {
+            self.daemon_pending = None;
+        }
+        self.expire_daemon_pending();
+    }
+
+    // Runs on every redraw tick too, so a change that never shows up still clears.
+    pub fn expire_daemon_pending(&mut self) {
+        if self
+            .daemon_pending
+            .as_ref()
+            .is_some_and(|p| p.since.elapsed() > CONFIRM_TIMEOUT)
+        {
+            self.daemon_pending = None;
+            self.show_toast(ToastKind::Error, "daemon change not confirmed by space-os");
+        }
+    }
+}
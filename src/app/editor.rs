@@ -0,0 +1,202 @@
+use super::AppState;
+
+// Cursor-aware editing of the input line. `input_cursor` is a byte offset into
+// `input_text`; `None` means the end, so code that replaces the text wholesale
+// (history, autocomplete, submit) doesn't have to touch it.
+
+fn is_word(c: char) -> bool {
+    !c.is_whitespace()
+}
+
+impl AppState {
+    pub fn cursor(&self) -> usize {
+        let len = self.input_text.len();
+        let mut pos = self.input_cursor.unwrap_or(len).min(len);
+        while !self.input_text.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        pos
+    }
+
+    pub fn set_cursor(&mut self, pos: usize) {
+        self.input_cursor = (pos < self.input_text.len()).then_some(pos);
+    }
+
+    // (line, column in chars) of the cursor, for rendering and line movement.
+    pub fn cursor_position(&self) -> (usize, usize) {
+        let before = &self.input_text[..self.cursor()];
+        let line = before.matches('\n').count();
+        let column = before
+            .rsplit('\n')
+            .next()
+            .map(|l| l.chars().count())
+            .unwrap_or(0);
+        (line, column)
+    }
+
+    fn line_start(&self) -> usize {
+        self.input_text[..self.cursor()]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or(0)
+    }
+
+    fn line_end(&self) -> usize {
+        let cursor = self.cursor();
+        self.input_text[cursor..]
+            .find('\n')
+            .map(|i| cursor + i)
+            .unwrap_or(self.input_text.len())
+    }
+
+    fn edited(&mut self) {
+        self.history_idx = None;
+    }
+
+    pub fn add_char(&mut self, ch: char) {
+        let cursor = self.cursor();
+        self.input_text.insert(cursor, ch);
+        self.set_cursor(cursor + ch.len_utf8());
+        self.edited();
+    }
+
+    pub fn insert_newline(&mut self) {
+        self.add_char('\n');
+    }
+
+    // Pasted text lands as-is at the cursor, newlines included; it is never submitted.
+    pub fn paste(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let cursor = self.cursor();
+        self.input_text.insert_str(cursor, &text);
+        self.set_cursor(cursor + text.len());
+        self.edited();
+    }
+
+    pub fn backspace(&mut self) {
+        let cursor = self.cursor();
+        if let Some(ch) = self.input_text[..cursor].chars().next_back() {
+            let start = cursor - ch.len_utf8();
+            self.input_text.remove(start);
+            self.set_cursor(start);
+        }
+        self.edited();
+    }
+
+    pub fn delete_forward(&mut self) {
+        let cursor = self.cursor();
+        if cursor < self.input_text.len() {
+            self.input_text.remove(cursor);
+            self.set_cursor(cursor);
+        }
+        self.edited();
+    }
+
+    fn delete_to(&mut self, start: usize) {
+        let cursor = self.cursor();
+        self.input_text.replace_range(start..cursor, "");
+        self.set_cursor(start);
+        self.edited();
+    }
+
+    // Ctrl+W: the whitespace-delimited word before the cursor.
+    pub fn delete_word_back(&mut self) {
+        let start = self.word_start();
+        self.delete_to(start);
+    }
+
+    // Ctrl+U: everything between the start of the current line and the cursor.
+    pub fn kill_line_back(&mut self) {
+        let start = self.line_start();
+        self.delete_to(start);
+    }
+
+    fn word_start(&self) -> usize {
+        let before = &self.input_text[..self.cursor()];
+        let trimmed = before.trim_end_matches(|c: char| !is_word(c));
+        trimmed
+            .rfind(|c: char| !is_word(c))
+            .map(|i| i + trimmed[i..].chars().next().map_or(1, char::len_utf8))
+            .unwrap_or(0)
+    }
+
+    fn word_end(&self) -> usize {
+        let cursor = self.cursor();
+        let after = &self.input_text[cursor..];
+        let skipped = after.len() - after.trim_start_matches(|c: char| !is_word(c)).len();
+        after[skipped..]
+            .find(|c: char| !is_word(c))
+            .map(|i| cursor + skipped + i)
+            .unwrap_or(self.input_text.len())
+    }
+
+    pub fn cursor_left(&mut self) {
+        let cursor = self.cursor();
+        if let Some(ch) = self.input_text[..cursor].chars().next_back() {
+            self.set_cursor(cursor - ch.len_utf8());
+        }
+    }
+
+    pub fn cursor_right(&mut self) {
+        let cursor = self.cursor();
+        if let Some(ch) = self.input_text[cursor..].chars().next() {
+            self.set_cursor(cursor + ch.len_utf8());
+        }
+    }
+
+    pub fn cursor_home(&mut self) {
+        let start = self.line_start();
+        self.set_cursor(start);
+    }
+
+    pub fn cursor_end(&mut self) {
+        let end = self.line_end();
+        self.set_cursor(end);
+    }
+
+    pub fn word_left(&mut self) {
+        let start = self.word_start();
+        self.set_cursor(start);
+    }
+
+    pub fn word_right(&mut self) {
+        let end = self.word_end();
+        self.set_cursor(end);
+    }
+
+    // Moves to the same column on the line above; false on the first line so
+    // the caller can fall back to history.
+    pub fn cursor_up(&mut self) -> bool {
+        let (line, column) = self.cursor_position();
+        if line == 0 {
+            return false;
+        }
+        self.move_to(line - 1, column);
+        true
+    }
+
+    pub fn cursor_down(&mut self) -> bool {
+        let (line, column) = self.cursor_position();
+        if line + 1 >= self.input_line_count() {
+            return false;
+        }
+        self.move_to(line + 1, column);
+        true
+    }
+
+    fn move_to(&mut self, line: usize, column: usize) {
+        let start: usize = self
+            .input_text
+            .split('\n')
+            .take(line)
+            .map(|l| l.len() + 1)
+            .sum();
+        let text = self.input_text[start..].split('\n').next().unwrap_or("");
+        let offset = text
+            .char_indices()
+            .nth(column)
+            .map(|(i, _)| i)
+            .unwrap_or(text.len());
+        self.set_cursor(start + offset);
+    }
+}
@@ -0,0 +1,143 @@
+use super::{AppState, HistorySearch};
+use crate::history;
+use std::path::PathBuf;
+
+// The `@agent` mention a line starts with, if any.
+pub fn mention(line: &str) -> Option<&str> {
+    let word = line.split(char::is_whitespace).next()?;
+    (word.len() > 1 && word.starts_with('@')).then_some(word)
+}
+
+impl AppState {
+    pub fn open_history(&mut self, path: Option<PathBuf>) {
+        if let Some(path) = &path {
+            self.input_history = history::load(path, self.limits.history);
+        }
+        self.history_path = path;
+    }
+
+    // Failing to write the file only costs persistence; the session keeps its history.
+    pub fn remember(&mut self, line: &str) {
+        history::push(&mut self.input_history, line, self.limits.history);
+        if let Some(path) = &self.history_path {
+            let _ = history::append(path, line, self.limits.history);
+        }
+    }
+
+    fn browses(&self, idx: usize) -> bool {
+        let entry = &self.input_history[idx];
+        entry.starts_with(&self.history_prefix) && *entry != self.history_prefix
+    }
+
+    fn recall(&mut self, idx: usize) {
+        self.history_idx = Some(idx);
+        self.input_text = self.input_history[idx].clone();
+        self.input_cursor = None;
+    }
+
+    pub fn history_prev(&mut self) {
+        let start = match self.history_idx {
+            Some(idx) => idx + 1,
+            None => {
+                self.history_prefix = self.input_text.clone();
+                0
+            }
+        };
+        if let Some(idx) = (start..self.input_history.len()).find(|&i| self.browses(i)) {
+            self.recall(idx);
+        }
+    }
+
+    // Past the newest match, the line goes back to what was typed.
+    pub fn history_next(&mut self) {
+        let Some(current) = self.history_idx else {
+            return;
+        };
+        match (0..current).rev().find(|&i| self.browses(i)) {
+            Some(idx) => self.recall(idx),
+            None => {
+                self.history_idx = None;
+                self.input_text = std::mem::take(&mut self.history_prefix);
+                self.input_cursor = None;
+            }
+        }
+    }
+
+    pub fn start_history_search(&mut self) {
+        self.cancel_autocomplete();
+        self.history_search = Some(HistorySearch {
+            scope: mention(&self.input_text).map(String::from),
+            original: self.input_text.clone(),
+            ..HistorySearch::default()
+        });
+        self.show_search_match();
+    }
+
+    // Index of the current match: newest first, case-insensitive, within the scope.
+    pub fn search_match(&self) -> Option<usize> {
+        let search = self.history_search.as_ref()?;
+        let query = search.query.to_lowercase();
+        self.input_history
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                search
+                    .scope
+                    .as_deref()
+                    .is_none_or(|scope| mention(entry) == Some(scope))
+                    && entry.to_lowercase().contains(&query)
+            })
+            .nth(search.skip)
+            .map(|(idx, _)| idx)
+    }
+
+    // Without a match the line keeps the last one, like readline's failing search.
+    fn show_search_match(&mut self) {
+        if let Some(idx) = self.search_match() {
+            self.input_text = self.input_history[idx].clone();
+            self.input_cursor = None;
+        }
+    }
+
+    pub fn search_push(&mut self, ch: char) {
+        if let Some(search) = &mut self.history_search {
+            search.query.push(ch);
+            search.skip = 0;
+        }
+        self.show_search_match();
+    }
+
+    pub fn search_backspace(&mut self) {
+        if let Some(search) = &mut self.history_search {
+            search.query.pop();
+            search.skip = 0;
+        }
+        self.show_search_match();
+    }
+
+    // Ctrl+R again: the next older match, staying put on the oldest.
+    pub fn search_older(&mut self) {
+        let Some(search) = &mut self.history_search else {
+            return;
+        };
+        search.skip += 1;
+        if self.search_match().is_none()
+            && let Some(search) = &mut self.history_search
+        {
+            search.skip -= 1;
+        }
+        self.show_search_match();
+    }
+
+    pub fn accept_history_search(&mut self) {
+        self.history_search = None;
+        self.history_idx = None;
+    }
+
+    pub fn cancel_history_search(&mut self) {
+        if let Some(search) = self.history_search.take() {
+            self.input_text = search.original;
+            self.input_cursor = None;
+        }
+    }
+}
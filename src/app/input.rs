@@ -1,46 +1,32 @@
-use super::AppState;
+use super::{AppState, InputMode};
 
 impl AppState {
-    pub fn add_char(&mut self, ch: char) {
-        self.input_text.push(ch);
-        self.history_idx = None;
-    }
-
-    pub fn backspace(&mut self) {
-        self.input_text.pop();
-        self.history_idx = None;
+    pub fn is_inserting(&self) -> bool {
+        self.input_mode == InputMode::Insert
     }
 
-    pub fn history_prev(&mut self) {
-        if self.input_history.is_empty() {
-            return;
-        }
-
-        match self.history_idx {
-            None => {
-                self.history_idx = Some(0);
-                self.input_text = self.input_history[0].clone();
+    // Enters insert mode, optionally typing `ch`. A `:` only starts a command on an empty line.
+    pub fn start_input(&mut self, ch: Option<char>) {
+        self.input_mode = InputMode::Insert;
+        match ch {
+            Some(':') if !self.input_text.is_empty() => {}
+            Some(ch) => {
+                self.add_char(ch);
+                self.detect_and_trigger_autocomplete();
             }
-            Some(idx) if idx + 1 < self.input_history.len() => {
-                self.history_idx = Some(idx + 1);
-                self.input_text = self.input_history[idx + 1].clone();
-            }
-            _ => {}
+            None => {}
         }
     }
 
-    pub fn history_next(&mut self) {
-        match self.history_idx {
-            Some(idx) if idx > 0 => {
-                self.history_idx = Some(idx - 1);
-                self.input_text = self.input_history[idx - 1].clone();
-            }
-            Some(0) => {
-                self.history_idx = None;
-                self.input_text.clear();
-            }
-            _ => {}
-        }
+    // Back to normal mode; the typed text stays for later.
+    pub fn leave_insert(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.cancel_autocomplete();
+        self.history_search = None;
+    }
+
+    pub fn is_command_mode(&self) -> bool {
+        self.input_text.starts_with(':')
     }
 
     pub fn submit_input(&mut self) -> Option<String> {
@@ -49,10 +35,12 @@ impl AppState {
         }
 
         let cmd = self.input_text.clone();
-        self.input_history.insert(0, cmd.clone());
+        self.remember(&cmd);
         self.input_text.clear();
+        self.input_cursor = None;
         self.history_idx = None;
         self.input_scroll_offset = 0;
+        self.input_mode = InputMode::Normal;
 
         Some(cmd)
     }
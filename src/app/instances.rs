@@ -0,0 +1,196 @@
+use super::{AppState, InstanceStatus};
+use crate::command::Command;
+use crate::live::LiveEvent;
+use crate::poll::Focus;
+use crate::record::Record;
+
+impl InstanceStatus {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            live: false,
+            endpoints: Vec::new(),
+        }
+    }
+
+    pub fn is_down(&self) -> bool {
+        !self.endpoints.is_empty() && self.endpoints.iter().all(|s| s.is_failing())
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        self.endpoints.iter().any(|s| s.is_unauthorized())
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        self.endpoints.iter().any(|s| s.is_failing())
+    }
+}
+
+impl AppState {
+    pub fn set_instances(&mut self, names: Vec<String>) {
+        self.instances = names.into_iter().map(InstanceStatus::new).collect();
+        self.instance = 0;
+    }
+
+    pub fn is_multi_instance(&self) -> bool {
+        self.instances.len() > 1
+    }
+
+    // Rows we haven't seen tagged yet belong to the current instance.
+    pub fn origin(&self, id: &str) -> usize {
+        self.origins.get(id).copied().unwrap_or(self.instance)
+    }
+
+    // Instance name to show next to an agent or spawn; only in the merged view.
+    pub fn instance_tag(&self, id: &str) -> Option<&str> {
+        if !self.merged {
+            return None;
+        }
+        self.instances.get(self.origin(id)).map(|i| i.name.as_str())
+    }
+
+    // The instance whose activity, right pane and daemon are on screen.
+    pub fn shown_instance(&self) -> usize {
+        match self.active_agent() {
+            Some(agent) if self.merged => self.origin(&agent.id),
+            _ => self.instance,
+        }
+    }
+
+    pub fn command_instance(&self, cmd: &Command) -> usize {
+        match cmd {
+            Command::Kill(id) | Command::Retry(id) => self
+                .spawns
+                .iter()
+                .find(|s| s.id.starts_with(id.as_str()))
+                .map(|s| self.origin(&s.id))
+                .unwrap_or_else(|| self.shown_instance()),
+            _ => self.shown_instance(),
+        }
+    }
+
+    pub fn focus_for(&self, instance: usize) -> Focus {
+        let mut focus = self.focus();
+        if instance != self.shown_instance() {
+            focus.activity = None;
+            focus.tail = None;
+            focus.ledger = None;
+        }
+        if self
+            .selected_spawn()
+            .is_none_or(|s| self.origin(&s.id) != instance)
+        {
+            focus.spawn_id = None;
+        }
+        focus.live = self.instances.get(instance).is_some_and(|i| i.live);
+        focus
+    }
+
+    fn takes_roster(&self, instance: usize) -> bool {
+        self.merged || instance == self.instance
+    }
+
+    // Replaces one instance's rows, keeping the others' in instance order.
+    fn combine<T: Clone>(
+        &self,
+        instance: usize,
+        current: &[T],
+        fresh: &[T],
+        id: fn(&T) -> &str,
+    ) -> Vec<T> {
+        (0..self.instances.len().max(1))
+            .flat_map(|i| {
+                if i == instance {
+                    fresh.to_vec()
+                } else {
+                    current
+                        .iter()
+                        .filter(|row| self.origin(id(row)) == i)
+                        .cloned()
+                        .collect()
+                }
+            })
+            .collect()
+    }
+
+    pub fn apply_instance_record(&mut self, instance: usize, record: &Record) -> bool {
+        match record {
+            Record::Agents { agents } => {
+                if !self.takes_roster(instance) {
+                    return false;
+                }
+                for agent in agents {
+                    self.origins.insert(agent.id.clone(), instance);
+                }
+                let agents = self.combine(instance, &self.agents, agents, |a| &a.id);
+                self.apply_record(&Record::Agents { agents })
+            }
+            Record::Spawns { spawns } => {
+                if !self.takes_roster(instance) {
+                    return false;
+                }
+                for spawn in spawns {
+                    self.origins.insert(spawn.id.clone(), instance);
+                }
+                let mut spawns = self.combine(instance, &self.spawns, spawns, |s| &s.id);
+                if self.merged {
+                    spawns.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+                }
+                self.apply_record(&Record::Spawns { spawns })
+            }
+            // Matched against the selected spawn's id, which is already instance-specific.
+            Record::SpawnEvents { .. } => self.apply_record(record),
+            _ if instance == self.shown_instance() => self.apply_record(record),
+            _ => false,
+        }
+    }
+
+    pub fn apply_instance_live(&mut self, instance: usize, event: LiveEvent) {
+        match &event {
+            LiveEvent::Agent(agent) if self.takes_roster(instance) => {
+                self.origins.insert(agent.id.clone(), instance);
+            }
+            LiveEvent::Spawn(spawn) if self.takes_roster(instance) => {
+                self.origins.insert(spawn.id.clone(), instance);
+            }
+            LiveEvent::Ledger(_) | LiveEvent::Tail(_) | LiveEvent::Daemon(_)
+                if instance == self.shown_instance() => {}
+            _ => return,
+        }
+        self.apply_live_event(event);
+    }
+
+    pub fn next_instance(&mut self) {
+        if !self.is_multi_instance() {
+            return;
+        }
+        self.instance = (self.instance + 1) % self.instances.len();
+        if !self.merged {
+            self.reset_view();
+        }
+    }
+
+    pub fn toggle_merged(&mut self) {
+        if !self.is_multi_instance() {
+            return;
+        }
+        self.merged = !self.merged;
+        self.reset_view();
+    }
+
+    fn reset_view(&mut self) {
+        self.clear_data();
+        self.origins.clear();
+        self.cached_at = None;
+        self.active_agent_idx = 0;
+        self.active_spawn_idx = 0;
+        self.selected_spawn_idx = None;
+        self.activity_scope = None;
+        self.activity_exhausted = false;
+        self.activity_scroll_offset = 0;
+        self.sidebar_scroll_offset = 0;
+        self.spawn_activity_scroll_offset = 0;
+        self.stream_scroll_offset = 0;
+        self.ledger_scroll_offset = 0;
+    }
+}
@@ -0,0 +1,82 @@
+use super::AppState;
+use super::merge::prepend_new;
+use crate::live::LiveEvent;
+use crate::schema::{Activity, Agent, Spawn, TailEntry};
+
+impl AppState {
+    pub fn apply_live_event(&mut self, event: LiveEvent) {
+        match event {
+            LiveEvent::Agent(agent) => self.upsert_agent(agent),
+            LiveEvent::Spawn(spawn) => self.upsert_spawn(spawn),
+            LiveEvent::Ledger(act) => self.push_ledger(act),
+            LiveEvent::Tail(entry) => self.push_tail(entry),
+            LiveEvent::Daemon(daemon) => self.set_daemon(daemon),
+        }
+    }
+
+    fn upsert_agent(&mut self, agent: Agent) {
+        self.agent_identities
+            .insert(agent.id.clone(), agent.identity.clone());
+        match self.agents.iter_mut().find(|a| a.id == agent.id) {
+            Some(existing) => *existing = agent,
+            None => self.agents.push(agent),
+        }
+    }
+
+    fn upsert_spawn(&mut self, spawn: Spawn) {
+        if let Some(existing) = self.spawns.iter_mut().find(|s| s.id == spawn.id) {
+            *existing = spawn;
+            return;
+        }
+
+        // New spawns arrive newest-first; shift indices so focus stays on the same spawn.
+        self.spawns.insert(0, spawn);
+        if self.spawns.len() > 1 {
+            self.active_spawn_idx += 1;
+        }
+        if let Some(idx) = self.selected_spawn_idx.as_mut() {
+            *idx += 1;
+        }
+    }
+
+    fn push_ledger(&mut self, act: Activity) {
+        if let Some(query) = self.activity_query(self.limits.activity)
+            && query.agent_id.as_ref().is_none_or(|id| *id == act.agent_id)
+        {
+            // Rows still showing the previous agent are dropped rather than mixed
+            // with the new one's; the poller fetches the new agent's snapshot.
+            if query.agent_id != self.activity_scope {
+                self.set_activity(query.agent_id, Vec::new());
+                self.activity_scroll_offset = 0;
+            }
+            if prepend_new(&mut self.activity, vec![act.clone()], self.limits.activity) > 0
+                && self.activity_scroll_offset > 0
+            {
+                self.activity_scroll_offset += 1;
+            }
+        }
+
+        if prepend_new(&mut self.ledger, vec![act], self.limits.activity) > 0
+            && self.ledger_scroll_offset > 0
+        {
+            self.ledger_scroll_offset += 1;
+        }
+    }
+
+    fn push_tail(&mut self, entry: TailEntry) {
+        let matches_agent = self.all_stream
+            || self
+                .active_agent()
+                .is_none_or(|agent| agent.identity == entry.agent);
+        if !matches_agent {
+            return;
+        }
+
+        self.stream.push(entry);
+        if self.stream.len() > self.limits.stream {
+            let excess = self.stream.len() - self.limits.stream;
+            self.stream.drain(..excess);
+            self.stream_scroll_offset = self.stream_scroll_offset.saturating_sub(excess);
+        }
+    }
+}
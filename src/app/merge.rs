@@ -0,0 +1,176 @@
+use super::{AppState, RightPane};
+use crate::poll::Focus;
+use crate::schema::{Activity, Agent, LedgerQuery, Spawn, TailEntry};
+use std::collections::HashMap;
+
+pub(super) fn same_entry(a: &Activity, b: &Activity) -> bool {
+    a.primitive_id == b.primitive_id
+        && a.agent_id == b.agent_id
+        && a.action == b.action
+        && a.field == b.field
+        && a.created_at == b.created_at
+}
+
+// Newest-first buffers: returns how many unseen entries were added at the front.
+pub(super) fn prepend_new(buf: &mut Vec<Activity>, fresh: Vec<Activity>, cap: usize) -> usize {
+    let cap = cap.max(buf.len());
+    let new: Vec<Activity> = fresh
+        .into_iter()
+        .filter(|act| !buf.iter().any(|old| same_entry(old, act)))
+        .collect();
+    let added = new.len();
+    if added > 0 {
+        buf.splice(0..0, new);
+        buf.truncate(cap);
+    }
+    added
+}
+
+fn anchor(offset: &mut usize, added: usize, len: usize) {
+    if *offset > 0 {
+        *offset = (*offset + added).min(len.saturating_sub(1));
+    }
+}
+
+fn delta(query: LedgerQuery, newest: Option<&Activity>) -> LedgerQuery {
+    match newest {
+        Some(act) => query.since(act.created_at.clone()),
+        None => query,
+    }
+}
+
+impl AppState {
+    pub fn activity_delta_query(&self, limit: usize) -> Option<LedgerQuery> {
+        let query = self.activity_query(limit)?;
+        if query.agent_id != self.activity_scope {
+            return Some(query);
+        }
+        Some(delta(query, self.activity.first()))
+    }
+
+    pub fn ledger_delta_query(&self, limit: usize) -> LedgerQuery {
+        delta(LedgerQuery::new(limit), self.ledger.first())
+    }
+
+    // What the background poller should fetch; `resync` is owned by the render loop.
+    pub fn focus(&self) -> Focus {
+        let tail = (self.right_pane == RightPane::Stream).then(|| match self.active_agent() {
+            Some(agent) if !self.all_stream => Some(agent.identity.clone()),
+            _ => None,
+        });
+        Focus {
+            paused: self.paused,
+            live: self.live,
+            activity: self.activity_delta_query(self.limits.activity),
+            tail,
+            ledger: (self.right_pane == RightPane::Ledger)
+                .then(|| self.ledger_delta_query(self.limits.activity)),
+            spawn_id: self.selected_spawn().map(|s| s.id.clone()),
+            daemon_check: self.daemon_checks,
+            ..Focus::default()
+        }
+    }
+
+    pub fn merge_activity(
+        &mut self,
+        agent_id: Option<String>,
+        is_delta: bool,
+        fresh: Vec<Activity>,
+    ) -> bool {
+        if !is_delta {
+            let before = self.activity.clone();
+            self.set_activity(agent_id, fresh);
+            return before != self.activity;
+        }
+        if agent_id != self.activity_scope {
+            return false;
+        }
+        let added = prepend_new(&mut self.activity, fresh, self.limits.activity);
+        anchor(&mut self.activity_scroll_offset, added, self.activity.len());
+        added > 0
+    }
+
+    pub fn merge_ledger(&mut self, is_delta: bool, fresh: Vec<Activity>) -> bool {
+        if !is_delta {
+            if self.ledger == fresh {
+                return false;
+            }
+            self.ledger = fresh;
+            self.ledger_scroll_offset = self
+                .ledger_scroll_offset
+                .min(self.ledger.len().saturating_sub(1));
+            return true;
+        }
+        let added = prepend_new(&mut self.ledger, fresh, self.limits.activity);
+        anchor(&mut self.ledger_scroll_offset, added, self.ledger.len());
+        added > 0
+    }
+
+    pub fn replace_stream(&mut self, fresh: Vec<TailEntry>) -> bool {
+        if self.stream == fresh {
+            return false;
+        }
+        // The tail is a sliding window: find how far it moved to keep the view in place.
+        let shift = (0..self.stream.len())
+            .find(|&k| {
+                let kept = &self.stream[k..];
+                fresh.len() >= kept.len() && fresh[..kept.len()] == *kept
+            })
+            .unwrap_or(self.stream.len());
+        self.stream_scroll_offset = self.stream_scroll_offset.saturating_sub(shift);
+        self.stream = fresh;
+        if self.stream.len() > self.limits.stream {
+            let excess = self.stream.len() - self.limits.stream;
+            self.stream.drain(..excess);
+            self.stream_scroll_offset = self.stream_scroll_offset.saturating_sub(excess);
+        }
+        true
+    }
+
+    pub fn replace_agents(&mut self, agents: Vec<Agent>) -> bool {
+        if self.agents == agents {
+            return false;
+        }
+        let active_id = self.active_agent().map(|a| a.id.clone());
+        self.agents = agents;
+        if let Some(idx) = active_id.and_then(|id| self.agents.iter().position(|a| a.id == id)) {
+            self.active_agent_idx = idx;
+        }
+        true
+    }
+
+    pub fn replace_identities(&mut self, identities: HashMap<String, String>) -> bool {
+        if self.agent_identities == identities {
+            return false;
+        }
+        self.agent_identities = identities;
+        true
+    }
+
+    pub fn replace_spawns(&mut self, spawns: Vec<Spawn>) -> bool {
+        if self.spawns == spawns {
+            return false;
+        }
+        let active_id = self.spawns.get(self.active_spawn_idx).map(|s| s.id.clone());
+        let selected_id = self.selected_spawn().map(|s| s.id.clone());
+        self.spawns = spawns;
+
+        let position =
+            |id: Option<String>| id.and_then(|id| self.spawns.iter().position(|s| s.id == id));
+        if let Some(idx) = position(active_id) {
+            self.active_spawn_idx = idx;
+        }
+        if let Some(idx) = position(selected_id) {
+            self.selected_spawn_idx = Some(idx);
+        }
+        true
+    }
+
+    pub fn replace_spawn_activity(&mut self, events: Vec<Activity>) -> bool {
+        if self.spawn_activity == events {
+            return false;
+        }
+        self.spawn_activity = events;
+        true
+    }
+}
@@ -1,16 +1,42 @@
+use crate::command::Command;
+use crate::config::{Config, Layout, Limits};
+use crate::keymap::Keymap;
 use crate::schema::{Activity, Agent, DaemonStatus, Spawn, TailEntry};
+use crate::search::{Pane, Query};
+use crate::source::{Endpoint, EndpointStats};
+use chrono::{DateTime, Utc};
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Instant;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RightPane {
     Stream,
     Ledger,
+    SpawnDetail,
 }
 
+mod actions;
 mod autocomplete;
+mod daemon;
+mod editor;
+mod history;
 mod input;
+mod instances;
+mod live;
+mod merge;
 mod navigation;
+mod paging;
+mod replay;
 mod scroll;
+mod search;
+mod toast;
+mod transcript;
+mod tree;
+
+pub use history::mention;
+pub use transcript::is_foldable;
+pub use tree::SpawnRow;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SidebarTab {
@@ -18,14 +44,125 @@ pub enum SidebarTab {
     Spawns,
 }
 
+// Normal: keys run keymap actions. Insert: keys type into the input bar.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum InputMode {
+    #[default]
+    Normal,
+    Insert,
+}
+
+// Ctrl+R reverse search over the input history.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistorySearch {
+    pub query: String,
+    // The `@agent` the line started with; only entries for that agent match.
+    pub scope: Option<String>,
+    // Older matches skipped by pressing Ctrl+R again.
+    pub skip: usize,
+    // The line before searching, put back on cancel.
+    pub original: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AutocompleteMode {
     Agent,
     File,
+    Command,
+    Argument,
+    Spawn,
+    Ledger,
+}
+
+// Dropdown decoration for one completion value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Hint {
+    pub icon: &'static str,
+    pub detail: String,
+    // The query also matches `detail`: ledger text, a spawn's agent.
+    pub searchable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Pending,
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub kind: ToastKind,
+    pub message: String,
+    pub shown_at: Instant,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Transcript {
+    pub spawn_id: String,
+    pub cursor: usize,
+    pub line_offset: usize,
+    // Activity ids (event positions in the spawn's response), so folds survive refreshes.
+    pub expanded: HashSet<i64>,
+    pub search: String,
+    pub searching: bool,
+}
+
+// A row found by global search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Hit {
+    pub pane: Pane,
+    pub index: usize,
+}
+
+// `/` search across activity, stream, ledger and the loaded transcript.
+#[derive(Debug, Clone, Default)]
+pub struct GlobalSearch {
+    pub input: String,
+    pub editing: bool,
+    pub query: Query,
+    // The hit n/N last moved to.
+    pub current: Option<Hit>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confirm {
+    pub prompt: String,
+    pub command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DaemonChange {
+    Enabled(bool),
+    Running(bool),
+    Concurrency(i32),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayStatus {
+    pub playhead: DateTime<Utc>,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub speed: f64,
+    pub playing: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstanceStatus {
+    pub name: String,
+    pub live: bool,
+    pub endpoints: Vec<EndpointStats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingDaemon {
+    pub change: DaemonChange,
+    pub since: Instant,
 }
 
 pub struct AppState {
     pub paused: bool,
+    pub live: bool,
     pub all_stream: bool,
     pub right_pane: RightPane,
     pub active_tab: SidebarTab,
@@ -33,6 +170,9 @@ pub struct AppState {
     pub active_spawn_idx: usize,
     pub selected_spawn_idx: Option<usize>,
     pub expanded_spawns: HashSet<String>,
+    pub spawn_tree: bool,
+    pub collapsed_spawns: HashSet<String>,
+    pub flagged_spawns: HashSet<String>,
 
     pub agents: Vec<Agent>,
     pub spawns: Vec<Spawn>,
@@ -42,28 +182,58 @@ pub struct AppState {
     pub ledger: Vec<Activity>,
     pub agent_identities: HashMap<String, String>,
     pub daemon: DaemonStatus,
+    pub daemon_pending: Option<PendingDaemon>,
+    pub daemon_checks: u64,
+    pub endpoints: Vec<EndpointStats>,
+    pub cached_at: Option<DateTime<Utc>>,
+    pub replay: Option<ReplayStatus>,
+    pub instances: Vec<InstanceStatus>,
+    pub instance: usize,
+    pub merged: bool,
+    pub origins: HashMap<String, usize>,
 
     pub activity_scroll_offset: usize,
+    pub activity_scope: Option<String>,
+    pub activity_older_requested: bool,
+    pub activity_exhausted: bool,
     pub sidebar_scroll_offset: usize,
     pub spawn_activity_scroll_offset: usize,
     pub stream_scroll_offset: usize,
     pub ledger_scroll_offset: usize,
 
+    pub input_mode: InputMode,
     pub input_text: String,
+    pub input_cursor: Option<usize>,
     pub input_history: Vec<String>,
     pub history_idx: Option<usize>,
+    // What was typed before browsing; Up/Down only visit entries starting with it.
+    pub history_prefix: String,
+    pub history_search: Option<HistorySearch>,
+    pub history_path: Option<PathBuf>,
     pub input_scroll_offset: usize,
 
     pub autocomplete_mode: Option<AutocompleteMode>,
     pub autocomplete_list: Vec<String>,
     pub autocomplete_idx: usize,
     pub autocomplete_query: String,
+    pub autocomplete_hints: HashMap<String, Hint>,
+
+    pub toast: Option<Toast>,
+    pub transcript: Option<Transcript>,
+    pub global_search: Option<GlobalSearch>,
+    pub confirm: Option<Confirm>,
+
+    pub help: bool,
+    pub keymap: Keymap,
+    pub limits: Limits,
+    pub layout: Layout,
 }
 
 impl AppState {
     pub fn new() -> Self {
         Self {
             paused: false,
+            live: false,
             all_stream: false,
             right_pane: RightPane::Stream,
             active_tab: SidebarTab::Spawns,
@@ -71,6 +241,9 @@ impl AppState {
             active_spawn_idx: 0,
             selected_spawn_idx: None,
             expanded_spawns: HashSet::new(),
+            spawn_tree: false,
+            collapsed_spawns: HashSet::new(),
+            flagged_spawns: HashSet::new(),
 
             agents: vec![],
             spawns: vec![],
@@ -80,22 +253,58 @@ impl AppState {
             ledger: vec![],
             agent_identities: HashMap::new(),
             daemon: DaemonStatus::default(),
+            daemon_pending: None,
+            daemon_checks: 0,
+            endpoints: Vec::new(),
+            cached_at: None,
+            replay: None,
+            instances: Vec::new(),
+            instance: 0,
+            merged: false,
+            origins: HashMap::new(),
 
             activity_scroll_offset: 0,
+            activity_scope: None,
+            activity_older_requested: false,
+            activity_exhausted: false,
             sidebar_scroll_offset: 0,
             spawn_activity_scroll_offset: 0,
             stream_scroll_offset: 0,
             ledger_scroll_offset: 0,
 
+            input_mode: InputMode::Normal,
             input_text: String::new(),
+            input_cursor: None,
             input_history: Vec::new(),
             history_idx: None,
+            history_prefix: String::new(),
+            history_search: None,
+            history_path: None,
             input_scroll_offset: 0,
 
             autocomplete_mode: None,
             autocomplete_list: Vec::new(),
             autocomplete_idx: 0,
             autocomplete_query: String::new(),
+            autocomplete_hints: HashMap::new(),
+
+            toast: None,
+            transcript: None,
+            global_search: None,
+            confirm: None,
+            help: false,
+            keymap: Keymap::default(),
+            limits: Limits::default(),
+            layout: Layout::default(),
+        }
+    }
+
+    pub fn with_config(config: &Config) -> Self {
+        Self {
+            keymap: config.keymap(),
+            limits: config.limits,
+            layout: config.layout,
+            ..Self::new()
         }
     }
 
@@ -103,6 +312,10 @@ impl AppState {
         self.paused = !self.paused;
     }
 
+    pub fn toggle_help(&mut self) {
+        self.help = !self.help;
+    }
+
     pub fn toggle_all_stream(&mut self) {
         self.all_stream = !self.all_stream;
         self.activity_scroll_offset = 0;
@@ -124,8 +337,38 @@ impl AppState {
         self.selected_spawn_idx.and_then(|idx| self.spawns.get(idx))
     }
 
+    pub fn is_stale(&self, endpoint: Endpoint) -> bool {
+        let stats = self.endpoints.iter().find(|s| s.endpoint == endpoint);
+        if stats.is_some_and(|s| s.is_failing()) {
+            return true;
+        }
+        // Cached data stays stale until the endpoint has answered once.
+        self.cached_at.is_some() && stats.is_none_or(|s| s.last_success.is_none())
+    }
+
+    pub fn api_down(&self) -> bool {
+        !self.endpoints.is_empty() && self.endpoints.iter().all(|s| s.is_failing())
+    }
+
+    // Auth rejections outrank other failures: retrying won't fix them.
+    pub fn worst_endpoint(&self) -> Option<&EndpointStats> {
+        self.endpoints
+            .iter()
+            .filter(|s| s.is_failing())
+            .max_by_key(|s| (s.is_unauthorized(), s.consecutive_failures))
+    }
+
+    // An endpoint that answered but had malformed rows dropped from the answer.
+    pub fn skipping_endpoint(&self) -> Option<&EndpointStats> {
+        self.endpoints.iter().find(|s| !s.rejected.is_empty())
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        self.endpoints.iter().any(|s| s.is_unauthorized())
+    }
+
     pub fn input_line_count(&self) -> usize {
-        self.input_text.lines().count().max(1)
+        self.input_text.split('\n').count()
     }
 }
 
@@ -16,11 +16,7 @@ impl AppState {
                     self.active_agent_idx = (self.active_agent_idx + 1) % self.agents.len();
                 }
             }
-            SidebarTab::Spawns => {
-                if !self.spawns.is_empty() {
-                    self.active_spawn_idx = (self.active_spawn_idx + 1) % self.spawns.len();
-                }
-            }
+            SidebarTab::Spawns => self.step_spawn_row(true),
         }
     }
 
@@ -35,15 +31,7 @@ impl AppState {
                     };
                 }
             }
-            SidebarTab::Spawns => {
-                if !self.spawns.is_empty() {
-                    self.active_spawn_idx = if self.active_spawn_idx == 0 {
-                        self.spawns.len() - 1
-                    } else {
-                        self.active_spawn_idx - 1
-                    };
-                }
-            }
+            SidebarTab::Spawns => self.step_spawn_row(false),
         }
     }
 
@@ -66,6 +54,7 @@ impl AppState {
             });
             self.spawn_activity.clear();
             self.spawn_activity_scroll_offset = 0;
+            self.right_pane = RightPane::SpawnDetail;
         }
     }
 
@@ -78,13 +67,15 @@ impl AppState {
             });
             self.spawn_activity.clear();
             self.spawn_activity_scroll_offset = 0;
+            self.right_pane = RightPane::SpawnDetail;
         }
     }
 
     pub fn toggle_right_pane(&mut self) {
         self.right_pane = match self.right_pane {
             RightPane::Stream => RightPane::Ledger,
-            RightPane::Ledger => RightPane::Stream,
+            RightPane::Ledger => RightPane::SpawnDetail,
+            RightPane::SpawnDetail => RightPane::Stream,
         };
         self.stream_scroll_offset = 0;
         self.ledger_scroll_offset = 0;
@@ -97,14 +88,35 @@ impl AppState {
             .iter()
             .position(|a| a.identity.starts_with(ch_lower))
         {
-            self.active_tab = SidebarTab::Agents;
-            self.active_agent_idx = idx;
-            self.all_stream = false;
-            self.activity_scroll_offset = 0;
-            self.sidebar_scroll_offset = 0;
+            self.focus_agent_idx(idx);
             true
         } else {
             false
         }
     }
+
+    pub fn focus_agent(&mut self, identity: &str) -> bool {
+        let Some(idx) = self.agents.iter().position(|a| a.identity == identity) else {
+            return false;
+        };
+        self.focus_agent_idx(idx);
+        true
+    }
+
+    fn focus_agent_idx(&mut self, idx: usize) {
+        self.active_tab = SidebarTab::Agents;
+        self.active_agent_idx = idx;
+        self.all_stream = false;
+        self.activity_scroll_offset = 0;
+        self.sidebar_scroll_offset = 0;
+    }
+
+    pub fn resolve_spawn_id(&self, prefix: &str) -> Option<String> {
+        let mut matches = self.spawns.iter().filter(|s| s.id.starts_with(prefix));
+        let first = matches.next()?;
+        if matches.next().is_some() {
+            return None;
+        }
+        Some(first.id.clone())
+    }
 }
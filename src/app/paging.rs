@@ -0,0 +1,68 @@
+use super::AppState;
+use super::merge::same_entry;
+use crate::schema::{Activity, LedgerQuery};
+
+impl AppState {
+    pub fn activity_query(&self, limit: usize) -> Option<LedgerQuery> {
+        if self.all_stream {
+            return Some(LedgerQuery::new(limit));
+        }
+        self.active_agent()
+            .map(|agent| LedgerQuery::new(limit).agent(agent.id.clone()))
+    }
+
+    pub fn set_activity(&mut self, agent_id: Option<String>, fresh: Vec<Activity>) {
+        if agent_id != self.activity_scope {
+            self.activity_scope = agent_id;
+            self.activity_exhausted = false;
+            self.activity = fresh;
+            return;
+        }
+
+        // Keep pages loaded by scrolling that are older than the refreshed window.
+        let older: Vec<Activity> = match fresh.last() {
+            Some(oldest) => self
+                .activity
+                .iter()
+                .filter(|act| act.created_at <= oldest.created_at)
+                .filter(|act| !fresh.iter().any(|f| same_entry(f, act)))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        self.activity = fresh;
+        self.activity.extend(older);
+    }
+
+    pub fn request_older_activity(&mut self) {
+        if !self.activity_exhausted {
+            self.activity_older_requested = true;
+        }
+    }
+
+    pub fn take_older_activity_query(&mut self) -> Option<LedgerQuery> {
+        if !std::mem::take(&mut self.activity_older_requested) {
+            return None;
+        }
+        let oldest = self.activity.last()?.created_at.clone();
+        self.activity_query(self.limits.fetch)
+            .map(|query| query.before(oldest))
+    }
+
+    pub fn append_activity_page(&mut self, agent_id: Option<String>, page: Vec<Activity>) {
+        if agent_id != self.activity_scope {
+            return;
+        }
+        // `before` is inclusive, so the page repeats rows at the boundary.
+        let page: Vec<Activity> = page
+            .into_iter()
+            .filter(|act| !self.activity.iter().any(|old| same_entry(old, act)))
+            .collect();
+        if page.is_empty() {
+            self.activity_exhausted = true;
+            return;
+        }
+        self.activity.extend(page);
+        self.scroll_activity_down();
+    }
+}
@@ -0,0 +1,73 @@
+use super::AppState;
+use crate::record::Record;
+use crate::schema::DaemonStatus;
+
+impl AppState {
+    // Returns whether anything visible changed.
+    pub fn apply_record(&mut self, record: &Record) -> bool {
+        match record {
+            Record::Agents { agents } => {
+                let identities = self.replace_identities(
+                    agents
+                        .iter()
+                        .map(|a| (a.id.clone(), a.identity.clone()))
+                        .collect(),
+                );
+                self.replace_agents(agents.clone()) || identities
+            }
+            Record::Spawns { spawns } => self.replace_spawns(spawns.clone()),
+            Record::Activity {
+                agent_id,
+                delta,
+                items,
+            } => {
+                let shown = match agent_id {
+                    None => self.all_stream,
+                    Some(id) => {
+                        !self.all_stream && self.active_agent().is_some_and(|a| &a.id == id)
+                    }
+                };
+                shown && self.merge_activity(agent_id.clone(), *delta, items.clone())
+            }
+            Record::ActivityPage { agent_id, items } => {
+                self.append_activity_page(agent_id.clone(), items.clone());
+                true
+            }
+            Record::Ledger { delta, items } => self.merge_ledger(*delta, items.clone()),
+            Record::SpawnEvents { spawn_id, items } => {
+                self.selected_spawn().is_some_and(|s| &s.id == spawn_id)
+                    && self.replace_spawn_activity(items.clone())
+            }
+            Record::Tail { agent, entries } => {
+                let shown = match agent {
+                    None => self.all_stream || self.active_agent().is_none(),
+                    Some(identity) => {
+                        !self.all_stream
+                            && self.active_agent().is_some_and(|a| &a.identity == identity)
+                    }
+                };
+                shown && self.replace_stream(entries.clone())
+            }
+            Record::Daemon { status } => {
+                let changed = *status != self.daemon;
+                self.set_daemon(status.clone());
+                changed
+            }
+            Record::Live { event } => {
+                self.apply_live_event((**event).clone());
+                true
+            }
+        }
+    }
+
+    pub fn clear_data(&mut self) {
+        self.agents.clear();
+        self.spawns.clear();
+        self.activity.clear();
+        self.spawn_activity.clear();
+        self.stream.clear();
+        self.ledger.clear();
+        self.agent_identities.clear();
+        self.daemon = DaemonStatus::default();
+    }
+}
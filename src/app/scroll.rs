@@ -3,6 +3,9 @@ use super::AppState;
 impl AppState {
     pub fn scroll_activity_down(&mut self) {
         let max_scroll = self.activity.len().saturating_sub(1);
+        if self.activity_scroll_offset >= max_scroll {
+            self.request_older_activity();
+        }
         self.activity_scroll_offset = (self.activity_scroll_offset + 1).min(max_scroll);
     }
 
@@ -0,0 +1,186 @@
+use super::{AppState, GlobalSearch, Hit, RightPane};
+use crate::schema::Activity;
+use crate::search::{Pane, Query, Row};
+
+fn activity_row<'a>(app_state: &'a AppState, act: &'a Activity, text: Vec<&'a str>) -> Row<'a> {
+    Row {
+        agent: app_state.resolve_identity(&act.agent_id),
+        kind: &act.primitive,
+        name: act.field.as_deref(),
+        spawn: act.spawn_id.as_deref(),
+        text,
+    }
+}
+
+fn present<'a>(fields: impl IntoIterator<Item = Option<&'a String>>) -> Vec<&'a str> {
+    fields.into_iter().flatten().map(String::as_str).collect()
+}
+
+impl AppState {
+    fn pane_len(&self, pane: Pane) -> usize {
+        match pane {
+            Pane::Activity => self.activity.len(),
+            Pane::Stream => self.stream.len(),
+            Pane::Ledger => self.ledger.len(),
+            Pane::Spawn => self.spawn_activity.len(),
+        }
+    }
+
+    fn search_row(&self, pane: Pane, index: usize) -> Option<Row<'_>> {
+        Some(match pane {
+            Pane::Activity => {
+                let a = self.activity.get(index)?;
+                let text = present([
+                    Some(&a.primitive),
+                    Some(&a.action),
+                    a.field.as_ref(),
+                    a.after.as_ref(),
+                ]);
+                activity_row(self, a, text)
+            }
+            Pane::Ledger => {
+                let a = self.ledger.get(index)?;
+                let text = present([Some(&a.primitive), Some(&a.action), a.after.as_ref()]);
+                activity_row(self, a, text)
+            }
+            Pane::Spawn => {
+                let a = self.spawn_activity.get(index)?;
+                let text = present([Some(&a.primitive), a.field.as_ref(), a.after.as_ref()]);
+                activity_row(self, a, text)
+            }
+            Pane::Stream => {
+                let e = self.stream.get(index)?;
+                Row {
+                    agent: &e.agent,
+                    kind: &e.entry_type,
+                    name: e.name.as_deref(),
+                    spawn: Some(&e.spawn),
+                    text: present([
+                        Some(&e.entry_type),
+                        e.name.as_ref(),
+                        e.content.as_ref(),
+                        e.args.as_ref(),
+                    ]),
+                }
+            }
+        })
+    }
+
+    pub fn global_search_query(&self) -> Option<&Query> {
+        self.global_search.as_ref().map(|s| &s.query)
+    }
+
+    // In pane order, then row order. Recomputed on demand since every poll can shift rows.
+    pub fn global_search_hits(&self) -> Vec<Hit> {
+        if self.global_search.is_none() {
+            return Vec::new();
+        }
+        Pane::ALL
+            .into_iter()
+            .flat_map(|pane| (0..self.pane_len(pane)).map(move |index| Hit { pane, index }))
+            .filter(|hit| self.is_search_hit(hit.pane, hit.index))
+            .collect()
+    }
+
+    pub fn is_search_hit(&self, pane: Pane, index: usize) -> bool {
+        self.global_search_query().is_some_and(|query| {
+            self.search_row(pane, index)
+                .is_some_and(|row| query.matches(pane, &row))
+        })
+    }
+
+    pub fn is_current_hit(&self, pane: Pane, index: usize) -> bool {
+        self.global_search
+            .as_ref()
+            .and_then(|s| s.current)
+            .is_some_and(|hit| hit == Hit { pane, index })
+    }
+
+    pub fn global_search_start(&mut self) {
+        self.global_search = Some(GlobalSearch {
+            editing: true,
+            ..GlobalSearch::default()
+        });
+    }
+
+    fn global_search_edit(&mut self, edit: impl FnOnce(&mut String)) {
+        if let Some(search) = self.global_search.as_mut() {
+            edit(&mut search.input);
+            search.query = Query::parse(&search.input);
+            search.current = None;
+        }
+    }
+
+    pub fn global_search_push(&mut self, ch: char) {
+        self.global_search_edit(|input| input.push(ch));
+    }
+
+    pub fn global_search_pop(&mut self) {
+        self.global_search_edit(|input| {
+            input.pop();
+        });
+    }
+
+    // Enter: stop editing and jump to the first hit. An empty query just closes.
+    pub fn global_search_commit(&mut self) {
+        let Some(search) = self.global_search.as_mut() else {
+            return;
+        };
+        if search.query.is_empty() {
+            self.global_search = None;
+            return;
+        }
+        search.editing = false;
+        self.global_search_next();
+    }
+
+    pub fn global_search_cancel(&mut self) {
+        self.global_search = None;
+    }
+
+    pub fn global_search_next(&mut self) {
+        let hits = self.global_search_hits();
+        let current = self.global_search.as_ref().and_then(|s| s.current);
+        let next = match current {
+            Some(current) => hits.iter().find(|h| **h > current).or(hits.first()),
+            None => hits.first(),
+        };
+        if let Some(hit) = next.copied() {
+            self.jump_to_hit(hit);
+        }
+    }
+
+    pub fn global_search_prev(&mut self) {
+        let hits = self.global_search_hits();
+        let current = self.global_search.as_ref().and_then(|s| s.current);
+        let prev = match current {
+            Some(current) => hits.iter().rev().find(|h| **h < current).or(hits.last()),
+            None => hits.last(),
+        };
+        if let Some(hit) = prev.copied() {
+            self.jump_to_hit(hit);
+        }
+    }
+
+    // Scrolls the hit's pane to it, switching the right pane if that's where it is.
+    fn jump_to_hit(&mut self, hit: Hit) {
+        match hit.pane {
+            Pane::Activity => self.activity_scroll_offset = hit.index,
+            Pane::Stream => {
+                self.right_pane = RightPane::Stream;
+                self.stream_scroll_offset = hit.index;
+            }
+            Pane::Ledger => {
+                self.right_pane = RightPane::Ledger;
+                self.ledger_scroll_offset = hit.index;
+            }
+            Pane::Spawn => {
+                self.right_pane = RightPane::SpawnDetail;
+                self.spawn_activity_scroll_offset = hit.index;
+            }
+        }
+        if let Some(search) = self.global_search.as_mut() {
+            search.current = Some(hit);
+        }
+    }
+}
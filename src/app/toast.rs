@@ -0,0 +1,20 @@
+use super::{AppState, Toast, ToastKind};
+use std::time::{Duration, Instant};
+
+const TOAST_TTL: Duration = Duration::from_secs(5);
+
+impl AppState {
+    pub fn show_toast(&mut self, kind: ToastKind, message: impl Into<String>) {
+        self.toast = Some(Toast {
+            kind,
+            message: message.into(),
+            shown_at: Instant::now(),
+        });
+    }
+
+    pub fn active_toast(&self) -> Option<&Toast> {
+        self.toast
+            .as_ref()
+            .filter(|t| t.kind == ToastKind::Pending || t.shown_at.elapsed() < TOAST_TTL)
+    }
+}
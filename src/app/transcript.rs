@@ -0,0 +1,208 @@
+use super::{AppState, Transcript};
+use crate::schema::Activity;
+
+pub fn is_foldable(act: &Activity) -> bool {
+    matches!(
+        act.primitive.as_str(),
+        "tool_call" | "tool" | "tool_result" | "result"
+    )
+}
+
+fn event_matches(act: &Activity, query: &str) -> bool {
+    let query = query.to_lowercase();
+    [
+        Some(act.primitive.as_str()),
+        act.field.as_deref(),
+        act.after.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|text| text.to_lowercase().contains(&query))
+}
+
+impl AppState {
+    pub fn open_transcript(&mut self) -> bool {
+        if self.selected_spawn_idx.is_none() {
+            if self.active_spawn_idx >= self.spawns.len() {
+                return false;
+            }
+            self.selected_spawn_idx = Some(self.active_spawn_idx);
+            self.spawn_activity.clear();
+            self.spawn_activity_scroll_offset = 0;
+        }
+        let Some(spawn) = self.selected_spawn() else {
+            return false;
+        };
+        self.transcript = Some(Transcript {
+            spawn_id: spawn.id.clone(),
+            ..Transcript::default()
+        });
+        true
+    }
+
+    pub fn close_transcript(&mut self) {
+        self.transcript = None;
+    }
+
+    pub fn transcript_next_event(&mut self) {
+        let len = self.spawn_activity.len();
+        if let Some(t) = self.transcript.as_mut() {
+            t.cursor = (t.cursor + 1).min(len.saturating_sub(1));
+            t.line_offset = 0;
+        }
+    }
+
+    pub fn transcript_prev_event(&mut self) {
+        if let Some(t) = self.transcript.as_mut() {
+            t.cursor = t.cursor.saturating_sub(1);
+            t.line_offset = 0;
+        }
+    }
+
+    pub fn transcript_first(&mut self) {
+        if let Some(t) = self.transcript.as_mut() {
+            t.cursor = 0;
+            t.line_offset = 0;
+        }
+    }
+
+    pub fn transcript_last(&mut self) {
+        let len = self.spawn_activity.len();
+        if let Some(t) = self.transcript.as_mut() {
+            t.cursor = len.saturating_sub(1);
+            t.line_offset = 0;
+        }
+    }
+
+    pub fn transcript_scroll_down(&mut self, lines: usize) {
+        if let Some(t) = self.transcript.as_mut() {
+            t.line_offset += lines;
+        }
+    }
+
+    pub fn transcript_scroll_up(&mut self, lines: usize) {
+        let Some(t) = self.transcript.as_mut() else {
+            return;
+        };
+        if t.line_offset >= lines {
+            t.line_offset -= lines;
+        } else {
+            t.line_offset = 0;
+            t.cursor = t.cursor.saturating_sub(1);
+        }
+    }
+
+    pub fn transcript_toggle_fold(&mut self) {
+        let Some(t) = self.transcript.as_mut() else {
+            return;
+        };
+        let Some(id) = self
+            .spawn_activity
+            .get(t.cursor)
+            .filter(|a| is_foldable(a))
+            .and_then(|a| a.id)
+        else {
+            return;
+        };
+        if !t.expanded.remove(&id) {
+            t.expanded.insert(id);
+        }
+    }
+
+    pub fn transcript_search_start(&mut self) {
+        if let Some(t) = self.transcript.as_mut() {
+            t.searching = true;
+            t.search.clear();
+        }
+    }
+
+    pub fn transcript_search_push(&mut self, ch: char) {
+        if let Some(t) = self.transcript.as_mut() {
+            t.search.push(ch);
+        }
+    }
+
+    pub fn transcript_search_pop(&mut self) {
+        if let Some(t) = self.transcript.as_mut() {
+            t.search.pop();
+        }
+    }
+
+    pub fn transcript_search_commit(&mut self) {
+        let Some(t) = self.transcript.as_mut() else {
+            return;
+        };
+        t.searching = false;
+        let cursor = t.cursor;
+        if self
+            .spawn_activity
+            .get(cursor)
+            .is_some_and(|act| event_matches(act, &t.search))
+        {
+            self.reveal_transcript_event(cursor);
+        } else {
+            self.transcript_next_match();
+        }
+    }
+
+    pub fn transcript_search_cancel(&mut self) {
+        if let Some(t) = self.transcript.as_mut() {
+            t.searching = false;
+            t.search.clear();
+        }
+    }
+
+    pub fn transcript_matches(&self) -> Vec<usize> {
+        let Some(t) = self.transcript.as_ref().filter(|t| !t.search.is_empty()) else {
+            return vec![];
+        };
+        self.spawn_activity
+            .iter()
+            .enumerate()
+            .filter(|(_, act)| event_matches(act, &t.search))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn transcript_next_match(&mut self) {
+        let matches = self.transcript_matches();
+        let Some(cursor) = self.transcript.as_ref().map(|t| t.cursor) else {
+            return;
+        };
+        let next = matches
+            .iter()
+            .find(|&&idx| idx > cursor)
+            .or(matches.first());
+        if let Some(&idx) = next {
+            self.reveal_transcript_event(idx);
+        }
+    }
+
+    pub fn transcript_prev_match(&mut self) {
+        let matches = self.transcript_matches();
+        let Some(cursor) = self.transcript.as_ref().map(|t| t.cursor) else {
+            return;
+        };
+        let prev = matches
+            .iter()
+            .rev()
+            .find(|&&idx| idx < cursor)
+            .or(matches.last());
+        if let Some(&idx) = prev {
+            self.reveal_transcript_event(idx);
+        }
+    }
+
+    fn reveal_transcript_event(&mut self, idx: usize) {
+        let foldable = self
+            .spawn_activity
+            .get(idx)
+            .filter(|a| is_foldable(a))
+            .and_then(|a| a.id);
+        if let Some(t) = self.transcript.as_mut() {
+            t.cursor = idx;
+            t.line_offset = 0;
+            t.expanded.extend(foldable);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::{AppState, SidebarTab};
+use crate::schema::Spawn;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnRow {
+    pub idx: usize,
+    pub depth: usize,
+    pub descendants: usize,
+    // The rest is aggregated over the spawn and everything it called.
+    pub failed: bool,
+    pub active: bool,
+    pub start: String,
+    // None while anything in the subtree is still active.
+    pub end: Option<String>,
+}
+
+impl SpawnRow {
+    fn new(idx: usize, spawn: &Spawn) -> Self {
+        let active = spawn.status == "active";
+        Self {
+            idx,
+            depth: 0,
+            descendants: 0,
+            failed: spawn.is_failed(),
+            active,
+            start: spawn.created_at.clone(),
+            end: (!active).then(|| {
+                spawn
+                    .last_active_at
+                    .clone()
+                    .unwrap_or_else(|| spawn.created_at.clone())
+            }),
+        }
+    }
+
+    fn absorb(&mut self, child: &SpawnRow) {
+        self.descendants += child.descendants + 1;
+        self.failed |= child.failed;
+        self.active |= child.active;
+        if child.start < self.start {
+            self.start = child.start.clone();
+        }
+        self.end = match (self.end.take(), &child.end) {
+            (Some(end), Some(other)) => Some(end.max(other.clone())),
+            _ => None,
+        };
+    }
+}
+
+impl AppState {
+    pub fn toggle_spawn_tree(&mut self) {
+        self.spawn_tree = !self.spawn_tree;
+        self.active_tab = SidebarTab::Spawns;
+        self.sidebar_scroll_offset = 0;
+    }
+
+    pub fn toggle_spawn_collapse(&mut self) {
+        let Some(spawn) = self.spawns.get(self.active_spawn_idx) else {
+            return;
+        };
+        let id = spawn.id.clone();
+        if !self.collapsed_spawns.remove(&id) {
+            self.collapsed_spawns.insert(id);
+        }
+    }
+
+    fn spawn_children(&self) -> (Vec<usize>, HashMap<usize, Vec<usize>>) {
+        let by_id: HashMap<&str, usize> = self
+            .spawns
+            .iter()
+            .enumerate()
+            .map(|(idx, s)| (s.id.as_str(), idx))
+            .collect();
+
+        let mut roots = Vec::new();
+        let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (idx, spawn) in self.spawns.iter().enumerate() {
+            match spawn
+                .caller_spawn_id
+                .as_deref()
+                .and_then(|caller| by_id.get(caller))
+            {
+                Some(&parent) if parent != idx => children.entry(parent).or_default().push(idx),
+                _ => roots.push(idx),
+            }
+        }
+
+        // Callers that loop back on each other (stale or partial data) form a cycle
+        // no root reaches; cut it at its first member so those spawns still show.
+        let mut reached = HashSet::new();
+        let mut stack = roots.clone();
+        for idx in 0..self.spawns.len() {
+            if stack.is_empty() && !reached.contains(&idx) {
+                for kids in children.values_mut() {
+                    kids.retain(|&k| k != idx);
+                }
+                roots.push(idx);
+                stack.push(idx);
+            }
+            while let Some(next) = stack.pop() {
+                if reached.insert(next) {
+                    stack.extend(children.get(&next).into_iter().flatten());
+                }
+            }
+        }
+        (roots, children)
+    }
+
+    // One post-order pass: every reachable spawn with its subtree folded in.
+    fn subtree_rows(
+        &self,
+        roots: &[usize],
+        children: &HashMap<usize, Vec<usize>>,
+    ) -> HashMap<usize, SpawnRow> {
+        let mut rows: HashMap<usize, SpawnRow> = HashMap::new();
+        let mut seen = HashSet::new();
+        let mut stack: Vec<(usize, bool)> = roots.iter().map(|&r| (r, false)).collect();
+        while let Some((idx, visited)) = stack.pop() {
+            if visited {
+                let mut row = SpawnRow::new(idx, &self.spawns[idx]);
+                for kid in children.get(&idx).into_iter().flatten() {
+                    if let Some(child) = rows.get(kid) {
+                        row.absorb(child);
+                    }
+                }
+                rows.insert(idx, row);
+                continue;
+            }
+            if !seen.insert(idx) {
+                continue;
+            }
+            stack.push((idx, true));
+            if let Some(kids) = children.get(&idx) {
+                stack.extend(kids.iter().map(|&k| (k, false)));
+            }
+        }
+        rows
+    }
+
+    pub fn visible_spawn_rows(&self) -> Vec<SpawnRow> {
+        if !self.spawn_tree {
+            return self
+                .spawns
+                .iter()
+                .enumerate()
+                .map(|(idx, spawn)| SpawnRow::new(idx, spawn))
+                .collect();
+        }
+
+        let (roots, children) = self.spawn_children();
+        let mut subtrees = self.subtree_rows(&roots, &children);
+        let mut rows = Vec::new();
+        let mut stack: Vec<(usize, usize)> = roots.into_iter().rev().map(|r| (r, 0)).collect();
+
+        while let Some((idx, depth)) = stack.pop() {
+            let Some(row) = subtrees.remove(&idx) else {
+                continue;
+            };
+            rows.push(SpawnRow { depth, ..row });
+            if self.collapsed_spawns.contains(&self.spawns[idx].id) {
+                continue;
+            }
+            if let Some(kids) = children.get(&idx) {
+                stack.extend(kids.iter().rev().map(|&k| (k, depth + 1)));
+            }
+        }
+        rows
+    }
+
+    pub(super) fn step_spawn_row(&mut self, forward: bool) {
+        let rows = self.visible_spawn_rows();
+        if rows.is_empty() {
+            return;
+        }
+        let pos = rows
+            .iter()
+            .position(|r| r.idx == self.active_spawn_idx)
+            .unwrap_or(0);
+        let next = if forward {
+            (pos + 1) % rows.len()
+        } else if pos == 0 {
+            rows.len() - 1
+        } else {
+            pos - 1
+        };
+        self.active_spawn_idx = rows[next].idx;
+    }
+}
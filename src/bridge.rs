@@ -0,0 +1,176 @@
+use std::env;
+use tokio::process::Command;
+
+const DEFAULT_CHANNEL: &str = "general";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub ok: bool,
+    pub message: String,
+}
+
+pub fn bridge_channel() -> String {
+    env::var("SPACE_BRIDGE_CHANNEL").unwrap_or_else(|_| DEFAULT_CHANNEL.to_string())
+}
+
+pub fn send_args(channel: &str, line: &str) -> Vec<String> {
+    let line = line.trim();
+    if let Some(rest) = line.strip_prefix("/bridge ") {
+        return split_words(rest);
+    }
+    vec!["send".to_string(), channel.to_string(), line.to_string()]
+}
+
+// Shell-style words: quotes group, backslash escapes; an unclosed quote runs to the end.
+fn split_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\\' => word.get_or_insert_default().extend(chars.next()),
+            '\'' => {
+                let word = word.get_or_insert_default();
+                word.extend(chars.by_ref().take_while(|&c| c != '\''));
+            }
+            '"' => {
+                let word = word.get_or_insert_default();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some(next @ ('"' | '\\')) => word.push(next),
+                            Some(next) => {
+                                word.push('\\');
+                                word.push(next);
+                            }
+                            None => word.push('\\'),
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            c => word.get_or_insert_default().push(c),
+        }
+    }
+    words.extend(word);
+    words
+}
+
+fn first_line(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("")
+        .to_string()
+}
+
+// Messages go out as the configured identity, like API writes; without one the
+// bridge keeps whatever `SPACE_IDENTITY` it inherits.
+fn bridge_command(line: &str, identity: Option<&str>) -> Command {
+    let mut command = Command::new("bridge");
+    command.args(send_args(&bridge_channel(), line));
+    if let Some(identity) = identity {
+        command.env("SPACE_IDENTITY", identity);
+    }
+    command
+}
+
+pub async fn send(line: &str, identity: Option<&str>) -> Outcome {
+    let output = bridge_command(line, identity).output().await;
+
+    match output {
+        Ok(out) if out.status.success() => {
+            let stdout = first_line(&out.stdout);
+            Outcome {
+                ok: true,
+                message: if stdout.is_empty() {
+                    "sent".to_string()
+                } else {
+                    stdout
+                },
+            }
+        }
+        Ok(out) => {
+            let stderr = first_line(&out.stderr);
+            Outcome {
+                ok: false,
+                message: if stderr.is_empty() {
+                    format!("bridge exited with {}", out.status)
+                } else {
+                    stderr
+                },
+            }
+        }
+        Err(e) => Outcome {
+            ok: false,
+            message: format!("failed to run bridge: {}", e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mention_is_sent_to_channel() {
+        assert_eq!(
+            send_args("general", "@hailot check the build"),
+            vec!["send", "general", "@hailot check the build"]
+        );
+    }
+
+    #[test]
+    fn explicit_bridge_command_passes_through() {
+        assert_eq!(
+            send_args("general", "/bridge send ops @zealot"),
+            vec!["send", "ops", "@zealot"]
+        );
+    }
+
+    #[test]
+    fn explicit_bridge_command_keeps_quoted_words_together() {
+        assert_eq!(
+            send_args(
+                "general",
+                r#"/bridge send ops "@zealot check the build" 'no $vars' a\ b """#
+            ),
+            vec![
+                "send",
+                "ops",
+                "@zealot check the build",
+                "no $vars",
+                "a b",
+                ""
+            ]
+        );
+        assert_eq!(
+            send_args("general", r#"/bridge send ops "say \"hi\" now"#),
+            vec!["send", "ops", r#"say "hi" now"#]
+        );
+    }
+
+    #[test]
+    fn configured_identity_is_passed_to_bridge() {
+        let identity = |command: Command| {
+            command
+                .as_std()
+                .get_envs()
+                .find(|(key, _)| *key == "SPACE_IDENTITY")
+                .and_then(|(_, value)| value.map(|v| v.to_string_lossy().into_owned()))
+        };
+        assert_eq!(
+            identity(bridge_command("hi", Some("operator"))).as_deref(),
+            Some("operator")
+        );
+        assert_eq!(identity(bridge_command("hi", None)), None);
+    }
+
+    #[test]
+    fn first_line_skips_blank_output() {
+        assert_eq!(first_line(b"\n  \nsent: 42\nmore"), "sent: 42");
+    }
+}
@@ -0,0 +1,91 @@
+use crate::app::AppState;
+use crate::config::Instance;
+use crate::schema::{Activity, Agent, DaemonStatus, Spawn, TailEntry};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const CACHE_FILE: &str = "space-cmd-cache.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub saved_at: DateTime<Utc>,
+    pub agents: Vec<Agent>,
+    pub spawns: Vec<Spawn>,
+    pub agent_identities: HashMap<String, String>,
+    pub ledger: Vec<Activity>,
+    pub activity: Vec<Activity>,
+    pub stream: Vec<TailEntry>,
+    pub daemon: DaemonStatus,
+}
+
+impl Snapshot {
+    pub fn capture(app_state: &AppState) -> Self {
+        Self {
+            saved_at: Utc::now(),
+            agents: app_state.agents.clone(),
+            spawns: app_state.spawns.clone(),
+            agent_identities: app_state.agent_identities.clone(),
+            ledger: app_state.ledger.clone(),
+            activity: app_state.activity.clone(),
+            stream: app_state.stream.clone(),
+            daemon: app_state.daemon.clone(),
+        }
+    }
+
+    pub fn restore(self, app_state: &mut AppState) {
+        app_state.agents = self.agents;
+        app_state.spawns = self.spawns;
+        app_state.agent_identities = self.agent_identities;
+        app_state.ledger = self.ledger;
+        app_state.activity = self.activity;
+        app_state.stream = self.stream;
+        app_state.daemon = self.daemon;
+        app_state.cached_at = Some(self.saved_at);
+    }
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("SPACE_CMD_CACHE") {
+        return (!path.is_empty()).then(|| PathBuf::from(path));
+    }
+    if let Ok(dir) = std::env::var("XDG_CACHE_HOME")
+        && !dir.is_empty()
+    {
+        return Some(PathBuf::from(dir).join(CACHE_FILE));
+    }
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".space").join(CACHE_FILE))
+}
+
+// The default instance keeps the plain cache file; named ones get a suffixed sibling.
+pub fn instance_path(instance: &Instance) -> Option<PathBuf> {
+    let path = default_path()?;
+    if instance.is_default() {
+        return Some(path);
+    }
+    let stem = path.file_stem()?.to_string_lossy().into_owned();
+    let name = match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, instance.name, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, instance.name),
+    };
+    Some(path.with_file_name(name))
+}
+
+pub fn load(path: &Path) -> Option<Snapshot> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+pub fn save(path: &Path, snapshot: &Snapshot) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string(snapshot).map_err(std::io::Error::other)?;
+
+    // Write then rename so a crash mid-write never leaves a truncated cache.
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
+}
@@ -0,0 +1,211 @@
+use crate::api::ApiClient;
+use crate::bridge::Outcome;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArgKind {
+    None,
+    Text,
+    Agent,
+    Spawn,
+    FailedSpawn,
+    Choice(&'static [&'static str]),
+    Number,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub summary: &'static str,
+    pub arg: ArgKind,
+}
+
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "task",
+        usage: ":task <content>",
+        summary: "create a task",
+        arg: ArgKind::Text,
+    },
+    CommandSpec {
+        name: "kill",
+        usage: ":kill <spawn>",
+        summary: "stop a running spawn",
+        arg: ArgKind::Spawn,
+    },
+    CommandSpec {
+        name: "retry",
+        usage: ":retry <spawn>",
+        summary: "re-run a failed spawn",
+        arg: ArgKind::FailedSpawn,
+    },
+    CommandSpec {
+        name: "pause",
+        usage: ":pause",
+        summary: "pause/resume updates",
+        arg: ArgKind::None,
+    },
+    CommandSpec {
+        name: "swarm",
+        usage: ":swarm on|off",
+        summary: "enable or disable the swarm",
+        arg: ArgKind::Choice(&["on", "off"]),
+    },
+    CommandSpec {
+        name: "daemon",
+        usage: ":daemon start|stop",
+        summary: "start or stop the swarm daemon",
+        arg: ArgKind::Choice(&["start", "stop"]),
+    },
+    CommandSpec {
+        name: "concurrency",
+        usage: ":concurrency <n>",
+        summary: "set swarm concurrency",
+        arg: ArgKind::Number,
+    },
+    CommandSpec {
+        name: "focus",
+        usage: ":focus <agent>",
+        summary: "focus an agent",
+        arg: ArgKind::Agent,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Task(String),
+    Kill(String),
+    Retry(String),
+    Pause,
+    Swarm(bool),
+    Daemon(bool),
+    Concurrency(u32),
+    Focus(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandError {
+    Unknown(String),
+    Usage(&'static str),
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::Unknown(name) => write!(f, "unknown command :{}", name),
+            CommandError::Usage(usage) => write!(f, "usage: {}", usage),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+pub fn spec(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+pub fn split(line: &str) -> Option<(&str, &str)> {
+    let body = line.trim_start().strip_prefix(':')?;
+    Some(match body.split_once(char::is_whitespace) {
+        Some((name, rest)) => (name, rest.trim()),
+        None => (body.trim_end(), ""),
+    })
+}
+
+pub fn parse(line: &str) -> Result<Command, CommandError> {
+    let Some((name, arg)) = split(line) else {
+        return Err(CommandError::Unknown(line.trim().to_string()));
+    };
+    let spec = spec(name).ok_or_else(|| CommandError::Unknown(name.to_string()))?;
+    let usage = CommandError::Usage(spec.usage);
+
+    match spec.arg {
+        ArgKind::None if !arg.is_empty() => return Err(usage),
+        ArgKind::Text if arg.is_empty() => return Err(usage),
+        ArgKind::Agent
+        | ArgKind::Spawn
+        | ArgKind::FailedSpawn
+        | ArgKind::Choice(_)
+        | ArgKind::Number
+            if arg.is_empty() || arg.contains(char::is_whitespace) =>
+        {
+            return Err(usage);
+        }
+        ArgKind::Choice(options) if !options.contains(&arg) => return Err(usage),
+        _ => {}
+    }
+
+    match spec.name {
+        "task" => Ok(Command::Task(arg.to_string())),
+        "kill" => Ok(Command::Kill(arg.to_string())),
+        "retry" => Ok(Command::Retry(arg.to_string())),
+        "pause" => Ok(Command::Pause),
+        "swarm" => Ok(Command::Swarm(arg == "on")),
+        "daemon" => Ok(Command::Daemon(arg == "start")),
+        "concurrency" => match arg.parse() {
+            Ok(n) if n > 0 => Ok(Command::Concurrency(n)),
+            _ => Err(usage),
+        },
+        "focus" => Ok(Command::Focus(arg.trim_start_matches('@').to_string())),
+        _ => Err(CommandError::Unknown(name.to_string())),
+    }
+}
+
+// Attributed to the configured identity, else to the human agent.
+pub async fn create_task(client: &ApiClient, content: &str) -> Result<String, String> {
+    let creator = match client.identity() {
+        Some(identity) => identity.to_string(),
+        None => {
+            client
+                .get_agents()
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .find(|a| a.agent_type == "human")
+                .ok_or_else(|| "no human agent found (required for identity)".to_string())?
+                .id
+        }
+    };
+    client
+        .create_task(content, &creator)
+        .await
+        .map(|_| "task created".to_string())
+        .map_err(|e| e.to_string())
+}
+
+pub async fn run(client: &ApiClient, cmd: Command) -> Outcome {
+    let result = match &cmd {
+        Command::Task(content) => create_task(client, content).await,
+        Command::Kill(spawn_id) => client
+            .kill_spawn(spawn_id)
+            .await
+            .map(|_| format!("killed {}", &spawn_id[..spawn_id.len().min(8)]))
+            .map_err(|e| e.to_string()),
+        Command::Retry(spawn_id) => client
+            .retry_spawn(spawn_id)
+            .await
+            .map(|_| format!("retrying {}", &spawn_id[..spawn_id.len().min(8)]))
+            .map_err(|e| e.to_string()),
+        Command::Swarm(enabled) => client
+            .set_swarm_enabled(*enabled)
+            .await
+            .map(|_| format!("swarm {}", if *enabled { "on" } else { "off" }))
+            .map_err(|e| e.to_string()),
+        Command::Daemon(start) => client
+            .set_daemon_running(*start)
+            .await
+            .map(|_| format!("daemon {}", if *start { "started" } else { "stopped" }))
+            .map_err(|e| e.to_string()),
+        Command::Concurrency(n) => client
+            .set_swarm_concurrency(*n)
+            .await
+            .map(|_| format!("concurrency {}", n))
+            .map_err(|e| e.to_string()),
+        Command::Pause | Command::Focus(_) => Ok(String::new()),
+    };
+
+    match result {
+        Ok(message) => Outcome { ok: true, message },
+        Err(message) => Outcome { ok: false, message },
+    }
+}
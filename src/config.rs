@@ -0,0 +1,426 @@
+use crate::keymap::{Action, KeySpec, Keymap};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CONFIG_FILE: &str = "config.toml";
+const DEFAULT_INSTANCE: &str = "default";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub api: Api,
+    pub refresh: Refresh,
+    pub limits: Limits,
+    pub layout: Layout,
+    pub health: Health,
+    // Normal-mode bindings, e.g. `kill = "X"` or `down = ["j", "down"]`.
+    pub keys: BTreeMap<Action, KeySpec>,
+    pub instances: Vec<Instance>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Api {
+    // Used by the default instance when `SPACE_API_URL` is unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub timeout_ms: u64,
+}
+
+impl Default for Api {
+    fn default() -> Self {
+        Self {
+            url: None,
+            timeout_ms: crate::api::TIMEOUT.as_millis() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Refresh {
+    pub stream_ms: u64,
+    // Activity, ledger and spawn events.
+    pub activity_ms: u64,
+    // Agents, spawns and daemon status.
+    pub roster_ms: u64,
+    // How often delta polls are replaced by a full refetch.
+    pub full_s: u64,
+}
+
+impl Default for Refresh {
+    fn default() -> Self {
+        Self {
+            stream_ms: 500,
+            activity_ms: 1000,
+            roster_ms: 2000,
+            full_s: 30,
+        }
+    }
+}
+
+impl Refresh {
+    pub fn full_interval(&self) -> Duration {
+        Duration::from_secs(self.full_s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    // Rows per tail, spawn events and older-activity request.
+    pub fetch: usize,
+    // Activity and ledger rows kept in memory (and asked for per poll).
+    pub activity: usize,
+    // Tail lines kept in memory.
+    pub stream: usize,
+    // Submitted lines kept in the history file.
+    pub history: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            fetch: 200,
+            activity: 500,
+            stream: 200,
+            history: 1000,
+        }
+    }
+}
+
+// Column widths in percent; they must add up to 100.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Layout {
+    pub sidebar: u16,
+    pub activity: u16,
+    pub right: u16,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            sidebar: 25,
+            activity: 50,
+            right: 25,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Health {
+    pub stale_days: u64,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self { stale_days: 7 }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Instance {
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub auth: AuthScheme,
+    // Prefer `token_env` so the secret stays out of the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+    // Sent as `SPACE_IDENTITY` on every write.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+    // PEM file trusted in addition to the system roots.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+    // Falls back to `api.timeout_ms`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuthScheme {
+    #[default]
+    Bearer,
+    ApiKey,
+}
+
+// A credential that never shows up in Debug output or serialized config.
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret(***)")
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("***")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Credential {
+    Bearer(Secret),
+    ApiKey(Secret),
+}
+
+impl Config {
+    pub fn parse(raw: &str) -> std::io::Result<Self> {
+        Self::parse_with(raw, &[])
+    }
+
+    // `overrides` are `key.path=value` pairs applied on top of the file, as if written in it.
+    pub fn parse_with(raw: &str, overrides: &[String]) -> std::io::Result<Self> {
+        let mut table: toml::Table = raw.parse().map_err(std::io::Error::other)?;
+        for spec in overrides {
+            apply_override(&mut table, spec)?;
+        }
+        let config: Config = table.try_into().map_err(std::io::Error::other)?;
+        config.validate().map_err(std::io::Error::other)?;
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        Self::load_with(path, &[])
+    }
+
+    // A missing file is the same as an empty one; errors name the file.
+    pub fn load_with(path: &Path, overrides: &[String]) -> std::io::Result<Self> {
+        let raw = match std::fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(std::io::Error::other(format!("{}: {}", path.display(), e))),
+        };
+        Self::parse_with(&raw, overrides)
+            .map_err(|e| std::io::Error::other(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.api.timeout_ms < 50 {
+            return Err("api.timeout_ms must be at least 50".to_string());
+        }
+        if let Some(url) = &self.api.url {
+            check_url("api.url", url)?;
+        }
+        for (key, ms) in [
+            ("refresh.stream_ms", self.refresh.stream_ms),
+            ("refresh.activity_ms", self.refresh.activity_ms),
+            ("refresh.roster_ms", self.refresh.roster_ms),
+        ] {
+            if ms < 100 {
+                return Err(format!("{} must be at least 100 (got {})", key, ms));
+            }
+        }
+        if self.refresh.full_s == 0 {
+            return Err("refresh.full_s must be at least 1".to_string());
+        }
+        for (key, n) in [
+            ("limits.fetch", self.limits.fetch),
+            ("limits.activity", self.limits.activity),
+            ("limits.stream", self.limits.stream),
+            ("limits.history", self.limits.history),
+        ] {
+            if n == 0 {
+                return Err(format!("{} must be at least 1", key));
+            }
+        }
+        let layout = self.layout;
+        let total =
+            u32::from(layout.sidebar) + u32::from(layout.activity) + u32::from(layout.right);
+        if total != 100 {
+            return Err(format!(
+                "layout.sidebar + layout.activity + layout.right must add up to 100 (got {})",
+                total
+            ));
+        }
+        if [layout.sidebar, layout.activity, layout.right].contains(&0) {
+            return Err("layout columns must each be at least 1".to_string());
+        }
+        if self.health.stale_days == 0 {
+            return Err("health.stale_days must be at least 1".to_string());
+        }
+        Keymap::new(&self.keys)?;
+
+        for (i, instance) in self.instances.iter().enumerate() {
+            if instance.name.is_empty() {
+                return Err("instance name must not be empty".to_string());
+            }
+            // Names end up in cache filenames.
+            if !instance
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(format!(
+                    "instance name {:?} may only use letters, digits, '-' and '_'",
+                    instance.name
+                ));
+            }
+            if self.instances[..i].iter().any(|o| o.name == instance.name) {
+                return Err(format!("duplicate instance name {:?}", instance.name));
+            }
+            check_url(&format!("instance {:?} url", instance.name), &instance.url)?;
+            if let Some(ms) = instance.timeout_ms
+                && ms < 50
+            {
+                return Err(format!(
+                    "instance.{}.timeout_ms must be at least 50 (got {})",
+                    instance.name, ms
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // Configured instances, or a single one from the environment when none are listed.
+    pub fn instances(&self) -> Vec<Instance> {
+        let mut instances = if self.instances.is_empty() {
+            let mut instance = Instance::from_env();
+            if std::env::var("SPACE_API_URL").is_err()
+                && let Some(url) = &self.api.url
+            {
+                instance.url = url.clone();
+            }
+            vec![instance]
+        } else {
+            self.instances.clone()
+        };
+        for instance in &mut instances {
+            instance.timeout_ms.get_or_insert(self.api.timeout_ms);
+        }
+        instances
+    }
+
+    // Everything in effect, with instances resolved; tokens print as `***`.
+    pub fn effective(&self) -> Self {
+        Self {
+            keys: self.keymap().specs(),
+            instances: self.instances(),
+            ..self.clone()
+        }
+    }
+
+    pub fn keymap(&self) -> Keymap {
+        Keymap::new(&self.keys).unwrap_or_default()
+    }
+}
+
+fn check_url(key: &str, url: &str) -> Result<(), String> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} must start with http:// or https:// (got {:?})",
+            key, url
+        ))
+    }
+}
+
+fn apply_override(table: &mut toml::Table, spec: &str) -> std::io::Result<()> {
+    let invalid = |why: &str| std::io::Error::other(format!("--set {:?}: {}", spec, why));
+    let (key, raw) = spec
+        .split_once('=')
+        .ok_or_else(|| invalid("expected key=value"))?;
+    // Bare words that aren't TOML literals are taken as strings.
+    let value = format!("v = {}", raw.trim())
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut t| t.remove("v"))
+        .unwrap_or_else(|| toml::Value::String(raw.trim().to_string()));
+
+    let mut path: Vec<&str> = key.trim().split('.').collect();
+    let last = path
+        .pop()
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| invalid("empty key"))?;
+    let mut current = table;
+    for part in path {
+        current = current
+            .entry(part)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| invalid(&format!("{} is not a section", part)))?;
+    }
+    current.insert(last.to_string(), value);
+    Ok(())
+}
+
+impl Instance {
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            ..Self::default()
+        }
+    }
+
+    // `SPACE_API_URL`, `SPACE_API_TOKEN`, `SPACE_IDENTITY` and `SPACE_CA_CERT`.
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        Self {
+            token_env: Some("SPACE_API_TOKEN".to_string()),
+            identity: var("SPACE_IDENTITY"),
+            ca_cert: var("SPACE_CA_CERT").map(PathBuf::from),
+            ..Self::new(DEFAULT_INSTANCE, crate::api::api_base_url())
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.name == DEFAULT_INSTANCE
+    }
+
+    // An inline token wins over `token_env`.
+    pub fn credential(&self) -> Option<Credential> {
+        let secret = self.token.clone().or_else(|| {
+            let name = self.token_env.as_deref()?;
+            std::env::var(name)
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(Secret::new)
+        })?;
+        Some(match self.auth {
+            AuthScheme::Bearer => Credential::Bearer(secret),
+            AuthScheme::ApiKey => Credential::ApiKey(secret),
+        })
+    }
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("SPACE_CMD_CONFIG") {
+        return (!path.is_empty()).then(|| PathBuf::from(path));
+    }
+    if let Ok(dir) = std::env::var("XDG_CONFIG_HOME")
+        && !dir.is_empty()
+    {
+        return Some(PathBuf::from(dir).join("space-cmd").join(CONFIG_FILE));
+    }
+    let home = std::env::var("HOME").ok()?;
+    Some(
+        PathBuf::from(home)
+            .join(".config")
+            .join("space-cmd")
+            .join(CONFIG_FILE),
+    )
+}
@@ -0,0 +1,223 @@
+use crate::api::{ApiClient, ApiError};
+use crate::schema::{DaemonStatus, TailEntry};
+use crate::wire::{
+    AgentPayload, LedgerPayload, SpawnEventPayload, SpawnEventsPayload, SpawnPayload,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Shape {
+    List,
+    Object,
+    // An object holding the list under this key.
+    Wrapped(&'static str),
+}
+
+pub struct Contract {
+    pub name: &'static str,
+    pub shape: Shape,
+    pub required: &'static [&'static str],
+    pub optional: &'static [&'static str],
+    decode: fn(Value) -> Result<(), serde_json::Error>,
+}
+
+fn decodes<T: DeserializeOwned>(value: Value) -> Result<(), serde_json::Error> {
+    serde_json::from_value::<T>(value).map(drop)
+}
+
+fn decodes_events(value: Value) -> Result<(), serde_json::Error> {
+    let wrapped: SpawnEventsPayload = serde_json::from_value(value)?;
+    wrapped
+        .events
+        .into_iter()
+        .try_for_each(decodes::<SpawnEventPayload>)
+}
+
+pub const AGENTS: Contract = Contract {
+    name: "agents",
+    shape: Shape::List,
+    required: &["id", "identity", "type", "created_at"],
+    optional: &[
+        "model",
+        "constitution",
+        "avatar_path",
+        "color",
+        "archived_at",
+    ],
+    decode: decodes::<Vec<AgentPayload>>,
+};
+
+pub const SPAWNS: Contract = Contract {
+    name: "spawns",
+    shape: Shape::List,
+    required: &["id", "agent_id", "status", "created_at"],
+    optional: &[
+        "project_id",
+        "caller_spawn_id",
+        "source",
+        "error",
+        "pid",
+        "session_id",
+        "summary",
+        "trace_hash",
+        "last_active_at",
+    ],
+    decode: decodes::<Vec<SpawnPayload>>,
+};
+
+pub const LEDGER: Contract = Contract {
+    name: "ledger",
+    shape: Shape::List,
+    required: &["id", "agent_id", "type", "created_at"],
+    optional: &["status", "content"],
+    decode: decodes::<Vec<LedgerPayload>>,
+};
+
+pub const SPAWN_EVENTS: Contract = Contract {
+    name: "spawn events",
+    shape: Shape::Wrapped("events"),
+    required: &["type", "timestamp"],
+    optional: &["agent_id", "name", "content", "args"],
+    decode: decodes_events,
+};
+
+pub const DAEMON: Contract = Contract {
+    name: "daemon",
+    shape: Shape::Object,
+    required: &["running", "enabled", "concurrency", "active_count"],
+    optional: &["pid", "last_skip"],
+    decode: decodes::<DaemonStatus>,
+};
+
+pub const TAIL: Contract = Contract {
+    name: "tail",
+    shape: Shape::List,
+    required: &["spawn", "agent", "type"],
+    optional: &["content", "name", "args", "ctx_pct"],
+    decode: decodes::<Vec<TailEntry>>,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub endpoint: String,
+    pub items: usize,
+    pub missing: BTreeSet<String>,
+    pub unknown: BTreeSet<String>,
+    pub error: Option<String>,
+}
+
+impl Report {
+    // Unknown fields are additive and don't break us; missing fields and decode errors do.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.error.is_none()
+    }
+}
+
+impl Contract {
+    pub fn check(&self, value: Value) -> Report {
+        let mut report = Report {
+            endpoint: self.name.to_string(),
+            ..Report::default()
+        };
+        let items = match (self.shape, &value) {
+            (Shape::List, Value::Array(items)) => items.iter().collect(),
+            (Shape::Object, Value::Object(_)) => vec![&value],
+            (Shape::Wrapped(key), Value::Object(obj)) => match obj.get(key) {
+                Some(Value::Array(items)) => items.iter().collect(),
+                _ => {
+                    report.missing.insert(key.to_string());
+                    Vec::new()
+                }
+            },
+            _ => {
+                report.error = Some(format!("unexpected {} payload", kind(&value)));
+                return report;
+            }
+        };
+
+        report.items = items.len();
+        for item in items {
+            let Value::Object(fields) = item else {
+                report.error = Some(format!("expected objects, got {}", kind(item)));
+                return report;
+            };
+            for name in self.required {
+                if fields.get(*name).is_none_or(Value::is_null) {
+                    report.missing.insert(name.to_string());
+                }
+            }
+            for name in fields.keys() {
+                if !self.required.contains(&name.as_str())
+                    && !self.optional.contains(&name.as_str())
+                {
+                    report.unknown.insert(name.clone());
+                }
+            }
+        }
+
+        // Catches type changes the field lists can't see.
+        if report.missing.is_empty()
+            && let Err(e) = (self.decode)(value)
+        {
+            report.error = Some(e.to_string());
+        }
+        report
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "list",
+        Value::Object(_) => "object",
+    }
+}
+
+pub async fn check(client: &ApiClient) -> Vec<Report> {
+    let limit = [("limit", "20".to_string())];
+    let checked = |contract: &Contract, value: Result<Value, ApiError>| match value {
+        Ok(value) => contract.check(value),
+        Err(e) => Report {
+            endpoint: contract.name.to_string(),
+            error: Some(e.to_string()),
+            ..Report::default()
+        },
+    };
+
+    // Spawn events need a spawn to sample; use the newest one if there is any.
+    let spawns = client.get_value("/api/spawns", &[]).await;
+    let sample = spawns
+        .as_ref()
+        .ok()
+        .and_then(|v| v.as_array()?.first()?.get("id")?.as_str().map(String::from));
+
+    let mut reports = vec![
+        checked(&AGENTS, client.get_value("/api/agents", &[]).await),
+        checked(&SPAWNS, spawns),
+    ];
+    if let Some(id) = sample {
+        let path = format!("/api/spawns/{}/events", id);
+        reports.push(checked(
+            &SPAWN_EVENTS,
+            client.get_value(&path, &limit).await,
+        ));
+    }
+    reports.push(checked(
+        &LEDGER,
+        client.get_value("/api/ledger", &limit).await,
+    ));
+    reports.push(checked(
+        &DAEMON,
+        client.get_value("/api/swarm/daemon", &[]).await,
+    ));
+    reports.push(checked(
+        &TAIL,
+        client.get_value("/api/swarm/tail", &limit).await,
+    ));
+    reports
+}
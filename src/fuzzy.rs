@@ -0,0 +1,73 @@
+// Subsequence matching for autocomplete, case-insensitive. Higher scores rank
+// first: matches at the start, on word boundaries and in runs beat scattered ones.
+
+const MATCH: i32 = 16;
+const CONSECUTIVE: i32 = 12;
+const BOUNDARY: i32 = 8;
+const PREFIX: i32 = 12;
+// Opening a gap costs this much, plus one per further skipped char.
+const GAP: i32 = 4;
+// A late first match costs one per leading char, up to this.
+const MAX_LEADING: i32 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub score: i32,
+    // Char indices into the candidate, for highlighting.
+    pub positions: Vec<usize>,
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_boundary(chars: &[char], i: usize) -> bool {
+    i == 0
+        || !chars[i - 1].is_alphanumeric()
+        || (chars[i - 1].is_lowercase() && chars[i].is_uppercase())
+}
+
+pub fn find(query: &str, candidate: &str) -> Option<Match> {
+    let query: Vec<char> = query.chars().map(fold).collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    let Some(&first) = query.first() else {
+        return Some(Match {
+            score: 0,
+            positions: Vec::new(),
+        });
+    };
+
+    // Greedy from every place the first char occurs; keep the best-scoring run.
+    (0..chars.len())
+        .filter(|&start| fold(chars[start]) == first)
+        .filter_map(|start| {
+            let mut positions = vec![start];
+            let mut next = start + 1;
+            for &q in &query[1..] {
+                let i = (next..chars.len()).find(|&i| fold(chars[i]) == q)?;
+                positions.push(i);
+                next = i + 1;
+            }
+            Some(score(&chars, positions))
+        })
+        .reduce(|best, m| if m.score > best.score { m } else { best })
+}
+
+fn score(chars: &[char], positions: Vec<usize>) -> Match {
+    let mut score = 0;
+    for (n, &i) in positions.iter().enumerate() {
+        score += MATCH;
+        if is_boundary(chars, i) {
+            score += BOUNDARY;
+        }
+        match n.checked_sub(1).map(|p| positions[p]) {
+            Some(prev) if prev + 1 == i => score += CONSECUTIVE,
+            Some(prev) => score -= GAP + (i - prev - 2) as i32,
+            None if i == 0 => score += PREFIX,
+            None => score -= (i as i32).min(MAX_LEADING),
+        }
+    }
+    // Shorter candidates win ties.
+    score -= (chars.len() / 8) as i32;
+    Match { score, positions }
+}
@@ -1,3 +1,4 @@
+use crate::source::DataSource;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -36,6 +37,8 @@ pub struct RepoHealthOptions {
     pub repos_dir: Option<PathBuf>,
     pub run_ci: bool,
     pub timeout_s: u64,
+    // A repo with no commits for this long counts as stale.
+    pub stale_days: u64,
 }
 
 impl Default for RepoHealthOptions {
@@ -45,6 +48,7 @@ impl Default for RepoHealthOptions {
             repos_dir: None,
             run_ci: false,
             timeout_s: 120,
+            stale_days: 7,
         }
     }
 }
@@ -194,7 +198,7 @@ async fn repo_health_for(repo: &Path, options: &RepoHealthOptions) -> RepoHealth
         details.push("Working tree dirty.".to_string());
     }
     if let Some(age) = last_commit_age_s
-        && age > 86400 * 7
+        && age > 86400 * options.stale_days as i64
     {
         details.push(format!("Repo stale: last commit {} days ago.", age / 86400));
     }
@@ -218,7 +222,7 @@ async fn repo_health_for(repo: &Path, options: &RepoHealthOptions) -> RepoHealth
     }
 }
 
-fn repo_score(repo: &RepoHealth, run_ci: bool) -> (u32, Vec<String>) {
+fn repo_score(repo: &RepoHealth, options: &RepoHealthOptions) -> (u32, Vec<String>) {
     let mut score: i32 = 100;
     let mut details = Vec::<String>::new();
 
@@ -233,12 +237,12 @@ fn repo_score(repo: &RepoHealth, run_ci: bool) -> (u32, Vec<String>) {
         details.push("Dirty working tree.".to_string());
     }
     if let Some(age) = repo.last_commit_age_s
-        && age > 86400 * 7
+        && age > 86400 * options.stale_days as i64
     {
         score -= 5;
-        details.push("No commits in 7d.".to_string());
+        details.push(format!("No commits in {}d.", options.stale_days));
     }
-    if run_ci {
+    if options.run_ci {
         match repo.ci_ok {
             Some(false) => {
                 score -= 30;
@@ -255,12 +259,12 @@ fn repo_score(repo: &RepoHealth, run_ci: bool) -> (u32, Vec<String>) {
     (score.clamp(0, 100) as u32, details)
 }
 
-pub async fn calculate_health(options: RepoHealthOptions) -> HealthScore {
-    let api_base_url = crate::api::api_base_url();
+pub async fn calculate_health(src: &impl DataSource, options: RepoHealthOptions) -> HealthScore {
+    let api_base_url = src.label();
     let now = Utc::now();
 
     let started = Instant::now();
-    let health = crate::api::get_health().await;
+    let health = src.get_health().await;
     let api_latency_ms = Some(started.elapsed().as_millis());
 
     let mut details = Vec::<String>::new();
@@ -280,7 +284,8 @@ pub async fn calculate_health(options: RepoHealthOptions) -> HealthScore {
         }
     }
 
-    let ledger_freshness_s = crate::api::get_ledger_activity(1)
+    let ledger_freshness_s = src
+        .get_ledger(&crate::schema::LedgerQuery::new(1))
         .await
         .ok()
         .and_then(|mut items| items.pop())
@@ -299,7 +304,8 @@ pub async fn calculate_health(options: RepoHealthOptions) -> HealthScore {
         Some(_) => {}
     }
 
-    let spawns_freshness_s = crate::api::get_spawns()
+    let spawns_freshness_s = src
+        .get_spawns()
         .await
         .ok()
         .and_then(|spawns| {
@@ -330,7 +336,7 @@ pub async fn calculate_health(options: RepoHealthOptions) -> HealthScore {
     let mut repos_score = 100u32;
     for repo in repos {
         let mut rh = repo_health_for(&repo, &options).await;
-        let (score, score_details) = repo_score(&rh, options.run_ci);
+        let (score, score_details) = repo_score(&rh, &options);
         rh.details.extend(score_details);
         let degraded = score < 100;
         repos_score = repos_score.min(score);
@@ -0,0 +1,47 @@
+use std::path::{Path, PathBuf};
+
+const HISTORY_FILE: &str = "space-cmd-history.json";
+
+pub fn default_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("SPACE_CMD_HISTORY") {
+        return (!path.is_empty()).then(|| PathBuf::from(path));
+    }
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".space").join(HISTORY_FILE))
+}
+
+// Newest first. A repeated line moves to the front instead of appearing twice.
+pub fn push(history: &mut Vec<String>, line: &str, cap: usize) {
+    history.retain(|entry| entry != line);
+    history.insert(0, line.to_string());
+    history.truncate(cap);
+}
+
+// A missing or unreadable file is an empty history.
+pub fn load(path: &Path, cap: usize) -> Vec<String> {
+    let mut history: Vec<String> = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+    history.truncate(cap);
+    history
+}
+
+pub fn save(path: &Path, history: &[String]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string(history).map_err(std::io::Error::other)?;
+
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
+}
+
+// Re-reads the file first so concurrent sessions add to each other's history
+// rather than overwriting it.
+pub fn append(path: &Path, line: &str, cap: usize) -> std::io::Result<()> {
+    let mut history = load(path, cap);
+    push(&mut history, line, cap);
+    save(path, &history)
+}
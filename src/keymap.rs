@@ -0,0 +1,397 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Everything a normal-mode key can do. Config keys are the kebab-case names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Quit,
+    Help,
+    Insert,
+    Command,
+    Mention,
+    File,
+    ClearInput,
+    HistorySearch,
+    Search,
+    SearchNext,
+    SearchPrev,
+    SwitchTab,
+    Down,
+    Up,
+    NextSpawn,
+    PrevSpawn,
+    ScrollPaneDown,
+    ScrollPaneUp,
+    ScrollActivityDown,
+    ScrollActivityUp,
+    Transcript,
+    Pause,
+    AllStream,
+    CyclePane,
+    Expand,
+    Tree,
+    Collapse,
+    Kill,
+    Retry,
+    FollowUp,
+    Swarm,
+    ConcurrencyUp,
+    ConcurrencyDown,
+    Daemon,
+    NextInstance,
+    Merged,
+}
+
+impl Action {
+    pub const ALL: [Action; 36] = [
+        Action::Quit,
+        Action::Help,
+        Action::Insert,
+        Action::Command,
+        Action::Mention,
+        Action::File,
+        Action::ClearInput,
+        Action::HistorySearch,
+        Action::Search,
+        Action::SearchNext,
+        Action::SearchPrev,
+        Action::SwitchTab,
+        Action::Down,
+        Action::Up,
+        Action::NextSpawn,
+        Action::PrevSpawn,
+        Action::ScrollPaneDown,
+        Action::ScrollPaneUp,
+        Action::ScrollActivityDown,
+        Action::ScrollActivityUp,
+        Action::Transcript,
+        Action::Pause,
+        Action::AllStream,
+        Action::CyclePane,
+        Action::Expand,
+        Action::Tree,
+        Action::Collapse,
+        Action::Kill,
+        Action::Retry,
+        Action::FollowUp,
+        Action::Swarm,
+        Action::ConcurrencyUp,
+        Action::ConcurrencyDown,
+        Action::Daemon,
+        Action::NextInstance,
+        Action::Merged,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::Insert => "insert",
+            Action::Command => "command",
+            Action::Mention => "mention",
+            Action::File => "file",
+            Action::ClearInput => "clear-input",
+            Action::HistorySearch => "history-search",
+            Action::Search => "search",
+            Action::SearchNext => "search-next",
+            Action::SearchPrev => "search-prev",
+            Action::SwitchTab => "switch-tab",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::NextSpawn => "next-spawn",
+            Action::PrevSpawn => "prev-spawn",
+            Action::ScrollPaneDown => "scroll-pane-down",
+            Action::ScrollPaneUp => "scroll-pane-up",
+            Action::ScrollActivityDown => "scroll-activity-down",
+            Action::ScrollActivityUp => "scroll-activity-up",
+            Action::Transcript => "transcript",
+            Action::Pause => "pause",
+            Action::AllStream => "all-stream",
+            Action::CyclePane => "cycle-pane",
+            Action::Expand => "expand",
+            Action::Tree => "tree",
+            Action::Collapse => "collapse",
+            Action::Kill => "kill",
+            Action::Retry => "retry",
+            Action::FollowUp => "follow-up",
+            Action::Swarm => "swarm",
+            Action::ConcurrencyUp => "concurrency-up",
+            Action::ConcurrencyDown => "concurrency-down",
+            Action::Daemon => "daemon",
+            Action::NextInstance => "next-instance",
+            Action::Merged => "merged",
+        }
+    }
+
+    pub fn summary(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Help => "Show this help",
+            Action::Insert => "Type in the input bar",
+            Action::Command => "Start a :command",
+            Action::Mention => "Start an @agent mention",
+            Action::File => "Start a /file path",
+            Action::ClearInput => "Clear the input bar",
+            Action::HistorySearch => "Search input history",
+            Action::Search => "Search activity, stream, ledger, events",
+            Action::SearchNext => "Next search hit",
+            Action::SearchPrev => "Previous search hit",
+            Action::SwitchTab => "Switch sidebar tab (agents ↔ spawns)",
+            Action::Down => "Next agent/spawn",
+            Action::Up => "Previous agent/spawn",
+            Action::NextSpawn => "Select next spawn (spawn detail)",
+            Action::PrevSpawn => "Select previous spawn",
+            Action::ScrollPaneDown => "Scroll right pane down",
+            Action::ScrollPaneUp => "Scroll right pane up",
+            Action::ScrollActivityDown => "Scroll activity down (loads older)",
+            Action::ScrollActivityUp => "Scroll activity up",
+            Action::Transcript => "Open transcript of the selected spawn",
+            Action::Pause => "Pause/resume polling",
+            Action::AllStream => "Toggle all-agents activity",
+            Action::CyclePane => "Cycle right pane",
+            Action::Expand => "Toggle spawn expansion",
+            Action::Tree => "Toggle spawn call tree",
+            Action::Collapse => "Collapse/expand tree node",
+            Action::Kill => "Kill focused spawn",
+            Action::Retry => "Retry focused failed spawn",
+            Action::FollowUp => "Flag spawn for follow-up",
+            Action::Swarm => "Toggle swarm enabled",
+            Action::ConcurrencyUp => "Raise swarm concurrency",
+            Action::ConcurrencyDown => "Lower swarm concurrency",
+            Action::Daemon => "Start/stop daemon",
+            Action::NextInstance => "Switch to next instance",
+            Action::Merged => "Toggle merged instance view",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::Help => &["?"],
+            Action::Insert => &["i"],
+            Action::Command => &[":"],
+            Action::Mention => &["@"],
+            Action::File => &["F"],
+            Action::ClearInput => &["esc"],
+            Action::HistorySearch => &["ctrl+r"],
+            Action::Search => &["/"],
+            Action::SearchNext => &["n"],
+            Action::SearchPrev => &["N"],
+            Action::SwitchTab => &["h", "l"],
+            Action::Down => &["j", "down"],
+            Action::Up => &["k", "up"],
+            Action::NextSpawn => &["ctrl+j"],
+            Action::PrevSpawn => &["ctrl+k"],
+            Action::ScrollPaneDown => &["J"],
+            Action::ScrollPaneUp => &["K"],
+            Action::ScrollActivityDown => &["pgdn"],
+            Action::ScrollActivityUp => &["pgup"],
+            Action::Transcript => &["enter"],
+            Action::Pause => &["space"],
+            Action::AllStream => &["a"],
+            Action::CyclePane => &["d"],
+            Action::Expand => &["e"],
+            Action::Tree => &["t"],
+            Action::Collapse => &["z"],
+            Action::Kill => &["x"],
+            Action::Retry => &["r"],
+            Action::FollowUp => &["f"],
+            Action::Swarm => &["S"],
+            Action::ConcurrencyUp => &["+", "="],
+            Action::ConcurrencyDown => &["-"],
+            Action::Daemon => &["D"],
+            Action::NextInstance => &["I"],
+            Action::Merged => &["M"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+const NAMED: &[(&str, KeyCode)] = &[
+    ("space", KeyCode::Char(' ')),
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("pgup", KeyCode::PageUp),
+    ("pgdn", KeyCode::PageDown),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("delete", KeyCode::Delete),
+];
+
+impl Key {
+    // `j`, `J`, `ctrl+j`, `alt+x`, `space`, `pgdn`, `f5`, `+`, `ctrl++`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (mods, name) = match spec.strip_suffix("++") {
+            Some(mods) => (mods, "+"),
+            None => match spec.rsplit_once('+') {
+                Some((mods, name)) if !name.is_empty() => (mods, name),
+                _ => ("", spec),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for m in mods.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                _ => return Err(format!("unknown modifier {:?} in {:?}", m, spec)),
+            };
+        }
+
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(ch), None) => KeyCode::Char(ch),
+            _ => {
+                let lower = name.to_ascii_lowercase();
+                match NAMED.iter().find(|(n, _)| *n == lower) {
+                    Some((_, code)) => *code,
+                    None => match lower.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                        Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                        _ => return Err(format!("unknown key {:?}", spec)),
+                    },
+                }
+            }
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    // Shift is folded into the character (`J`), so it's ignored on char keys.
+    pub fn from_event(event: &KeyEvent) -> Self {
+        let mut modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if !matches!(event.code, KeyCode::Char(_)) {
+            modifiers |= event.modifiers & KeyModifiers::SHIFT;
+        }
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+}
+
+impl std::fmt::Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            f.write_str("shift+")?;
+        }
+        match NAMED.iter().find(|(_, code)| *code == self.code) {
+            Some((name, _)) => f.write_str(name),
+            None => match self.code {
+                KeyCode::Char(ch) => write!(f, "{}", ch),
+                KeyCode::F(n) => write!(f, "f{}", n),
+                other => write!(f, "{:?}", other),
+            },
+        }
+    }
+}
+
+// One key or a list of keys in config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeySpec {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySpec {
+    fn keys(&self) -> &[String] {
+        match self {
+            KeySpec::One(key) => std::slice::from_ref(key),
+            KeySpec::Many(keys) => keys,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<Key>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new()).expect("default keymap is valid")
+    }
+}
+
+impl Keymap {
+    // Each entry in `overrides` replaces all default keys of that action.
+    pub fn new(overrides: &BTreeMap<Action, KeySpec>) -> Result<Self, String> {
+        let mut bindings = Vec::new();
+        for action in Action::ALL {
+            let keys = match overrides.get(&action) {
+                Some(spec) => spec
+                    .keys()
+                    .iter()
+                    .map(|k| Key::parse(k).map_err(|e| format!("keys.{}: {}", action.name(), e)))
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => action
+                    .default_keys()
+                    .iter()
+                    .map(|k| Key::parse(k).expect("default key parses"))
+                    .collect(),
+            };
+            bindings.push((action, keys));
+        }
+
+        for (i, (action, keys)) in bindings.iter().enumerate() {
+            for key in keys {
+                if let Some((other, _)) = bindings[..i].iter().find(|(_, ks)| ks.contains(key)) {
+                    return Err(format!(
+                        "key `{}` is bound to both {} and {}; rebind one of them under [keys]",
+                        key,
+                        other.name(),
+                        action.name()
+                    ));
+                }
+            }
+        }
+        Ok(Self { bindings })
+    }
+
+    pub fn action(&self, event: &KeyEvent) -> Option<Action> {
+        let key = Key::from_event(event);
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or_default()
+    }
+
+    // Every binding as config would spell it, for `config show`.
+    pub fn specs(&self) -> BTreeMap<Action, KeySpec> {
+        self.bindings
+            .iter()
+            .map(|(action, keys)| {
+                (
+                    *action,
+                    KeySpec::Many(keys.iter().map(Key::to_string).collect()),
+                )
+            })
+            .collect()
+    }
+}
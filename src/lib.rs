@@ -1,7 +1,22 @@
 pub mod api;
 pub mod app;
+pub mod bridge;
+pub mod cache;
+pub mod command;
+pub mod config;
+pub mod contract;
+pub mod fuzzy;
 pub mod health;
+pub mod history;
+pub mod keymap;
+pub mod live;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod poll;
+pub mod record;
 pub mod schema;
+pub mod search;
 pub mod source;
 pub mod time;
 pub mod ui;
+pub mod wire;
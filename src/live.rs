@@ -0,0 +1,153 @@
+use crate::api::ApiClient;
+use crate::schema::{Activity, Agent, DaemonStatus, Spawn, TailEntry};
+use crate::wire::{AgentPayload, LedgerPayload, SpawnPayload};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::Connector;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+const BACKOFF_MIN: Duration = Duration::from_millis(500);
+const BACKOFF_MAX: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "lowercase")]
+pub enum LiveEvent {
+    Agent(Agent),
+    Spawn(Spawn),
+    Ledger(Activity),
+    Tail(TailEntry),
+    Daemon(DaemonStatus),
+}
+
+#[derive(Debug, Clone)]
+pub enum LiveUpdate {
+    Connected,
+    Disconnected,
+    Event(Box<LiveEvent>),
+}
+
+pub fn ws_url(base: &str) -> String {
+    let base = base.trim_end_matches('/');
+    let base = if let Some(rest) = base.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = base.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        base.to_string()
+    };
+    format!("{}/ws/events", base)
+}
+
+pub fn parse_event(text: &str) -> Option<LiveEvent> {
+    let v: serde_json::Value = serde_json::from_str(text).ok()?;
+    let data = v.get("data")?.clone();
+    match v["type"].as_str()? {
+        "agent" => serde_json::from_value::<AgentPayload>(data)
+            .ok()
+            .map(|p| LiveEvent::Agent(p.into())),
+        "spawn" => serde_json::from_value::<SpawnPayload>(data)
+            .ok()
+            .map(|p| LiveEvent::Spawn(p.into())),
+        "ledger" => serde_json::from_value::<LedgerPayload>(data)
+            .ok()
+            .map(|p| LiveEvent::Ledger(p.into())),
+        "tail" => serde_json::from_value(data).ok().map(LiveEvent::Tail),
+        "daemon" => serde_json::from_value(data).ok().map(LiveEvent::Daemon),
+        _ => None,
+    }
+}
+
+// Trusts the client's extra CA on top of the system roots; None keeps the default connector.
+fn tls_connector(client: &ApiClient) -> Option<Connector> {
+    let pem = String::from_utf8_lossy(client.ca_pem()?).into_owned();
+    let mut builder = native_tls::TlsConnector::builder();
+    for block in pem.split_inclusive("-----END CERTIFICATE-----") {
+        if let Ok(cert) = native_tls::Certificate::from_pem(block.trim().as_bytes()) {
+            builder.add_root_certificate(cert);
+        }
+    }
+    builder.build().ok().map(Connector::NativeTls)
+}
+
+async fn connect(
+    client: &ApiClient,
+) -> Option<
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
+> {
+    let mut request = ws_url(client.base_url()).into_client_request().ok()?;
+    request.headers_mut().extend(client.auth_headers().clone());
+    tokio_tungstenite::connect_async_tls_with_config(request, None, false, tls_connector(client))
+        .await
+        .ok()
+        .map(|(socket, _)| socket)
+}
+
+pub async fn run(client: ApiClient, tx: mpsc::UnboundedSender<LiveUpdate>) {
+    let mut backoff = BACKOFF_MIN;
+
+    loop {
+        if let Some(mut socket) = connect(&client).await {
+            backoff = BACKOFF_MIN;
+            if tx.send(LiveUpdate::Connected).is_err() {
+                return;
+            }
+
+            while let Some(Ok(msg)) = socket.next().await {
+                let Message::Text(text) = msg else {
+                    continue;
+                };
+                if let Some(event) = parse_event(&text)
+                    && tx.send(LiveUpdate::Event(Box::new(event))).is_err()
+                {
+                    return;
+                }
+            }
+
+            if tx.send(LiveUpdate::Disconnected).is_err() {
+                return;
+            }
+        }
+
+        if tx.is_closed() {
+            return;
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(BACKOFF_MAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ws_url_swaps_scheme() {
+        assert_eq!(
+            ws_url("http://localhost:8228"),
+            "ws://localhost:8228/ws/events"
+        );
+        assert_eq!(
+            ws_url("https://space.example/"),
+            "wss://space.example/ws/events"
+        );
+    }
+
+    #[test]
+    fn parse_tail_event() {
+        let event = parse_event(
+            r#"{"type":"tail","data":{"spawn":"s1","agent":"zealot","type":"text","content":"hi"}}"#,
+        );
+        match event {
+            Some(LiveEvent::Tail(entry)) => assert_eq!(entry.agent, "zealot"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_unknown_event_is_ignored() {
+        assert!(parse_event(r#"{"type":"mystery","data":{}}"#).is_none());
+    }
+}
@@ -1,21 +1,58 @@
 use clap::{Parser, Subcommand};
 use crossterm::{
-    event::{Event, EventStream, KeyCode, KeyModifiers},
+    event::{
+        DisableBracketedPaste, EnableBracketedPaste, Event, EventStream, KeyCode, KeyEvent,
+        KeyModifiers, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+        supports_keyboard_enhancement,
+    },
 };
 use futures::StreamExt;
 use ratatui::{Terminal, backend::CrosstermBackend};
-use space_cmd::app::{AppState, RightPane};
+use space_cmd::api::ApiClient;
+use space_cmd::app::{AppState, RightPane, ToastKind};
+use space_cmd::bridge;
+use space_cmd::cache::Snapshot;
+use space_cmd::command::{self, Command};
+use space_cmd::config::{self, Config, Instance};
+use space_cmd::contract;
 use space_cmd::health;
-use space_cmd::source::Source;
+use space_cmd::history;
+use space_cmd::keymap::Action;
+use space_cmd::live::{self, LiveUpdate};
+#[cfg(feature = "mock")]
+use space_cmd::mock;
+use space_cmd::poll::{self, Focus};
+use space_cmd::record::{self, Record, Recorder, Replay};
+use space_cmd::source::{DataSource, FixtureSource, Source};
 use space_cmd::ui::render_ui;
-use std::{io, time::Duration};
+use std::{
+    io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc, watch};
 
 #[derive(Parser)]
 #[command(name = "space-cmd")]
 #[command(about = "Command center for space agents", long_about = None)]
 struct Cli {
+    /// Read data from a fixture file instead of the space-os API
+    #[arg(long, global = true)]
+    fixture: Option<std::path::PathBuf>,
+
+    /// Config file (default: ~/.config/space-cmd/config.toml)
+    #[arg(long, global = true)]
+    config: Option<std::path::PathBuf>,
+
+    /// Override a config value, e.g. `--set refresh.stream_ms=250` (repeatable)
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    overrides: Vec<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -54,12 +91,72 @@ enum Commands {
         /// Task content
         content: String,
     },
+
+    /// Run the TUI and record everything it receives to a JSONL log
+    Record {
+        /// Log file to write
+        file: std::path::PathBuf,
+    },
+
+    /// Replay a recorded session log in the TUI
+    Replay {
+        /// Log file written by `record`
+        file: std::path::PathBuf,
+    },
+
+    /// Talk to the space-os API directly
+    Api {
+        #[command(subcommand)]
+        command: ApiCommand,
+    },
+
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// Serve a fake space-os API from a scenario file
+    #[cfg(feature = "mock")]
+    MockServer {
+        /// Scenario file (fixture data plus latency, failures and spawn lifecycles)
+        #[arg(long)]
+        scenario: Option<std::path::PathBuf>,
+
+        /// Port to listen on
+        #[arg(long, default_value_t = 8228)]
+        port: u16,
+    },
+}
+
+#[derive(Subcommand)]
+enum ApiCommand {
+    /// Compare live payloads against the fields space-cmd expects
+    Check,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the effective configuration (file, overrides and defaults)
+    Show,
+}
+
+const CACHE_INTERVAL: Duration = Duration::from_secs(5);
+const REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
+type CommandResult = (Option<Command>, bridge::Outcome);
+
+// Where command outcomes go, and one API client per instance to run them against.
+struct Dispatch {
+    outcome_tx: mpsc::UnboundedSender<CommandResult>,
+    clients: Vec<ApiClient>,
 }
 
 fn handle_scroll_down(app_state: &mut AppState) {
     match app_state.right_pane {
         RightPane::Stream => app_state.scroll_stream_down(),
         RightPane::Ledger => app_state.scroll_ledger_down(),
+        RightPane::SpawnDetail => app_state.scroll_spawn_activity_down(),
     }
 }
 
@@ -67,12 +164,20 @@ fn handle_scroll_up(app_state: &mut AppState) {
     match app_state.right_pane {
         RightPane::Stream => app_state.scroll_stream_up(),
         RightPane::Ledger => app_state.scroll_ledger_up(),
+        RightPane::SpawnDetail => app_state.scroll_spawn_activity_up(),
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
+    let (config_path, config) = match load_config(&cli) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("space-cmd: invalid config: {}", e);
+            std::process::exit(2);
+        }
+    };
 
     match cli.command {
         Some(Commands::Health {
@@ -88,8 +193,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 repos_dir,
                 run_ci: ci,
                 timeout_s,
+                stale_days: config.health.stale_days,
+            };
+            let result = match &cli.fixture {
+                Some(path) => health::calculate_health(&FixtureSource::load(path)?, options).await,
+                None => health::calculate_health(&primary_client(&config)?, options).await,
             };
-            let result = health::calculate_health(options).await;
             println!("Health Score: {}/100", result.score);
             println!(
                 "API: {} (ok: {}, latency: {:?}ms)",
@@ -159,197 +268,818 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             Ok(())
         }
         Some(Commands::Task { content }) => {
-            let human_agent = space_cmd::api::get_human_agent().await?;
-            if let Some(agent) = human_agent {
-                match space_cmd::api::create_task(&content, &agent.id).await {
-                    Ok(_) => println!("Task created."),
-                    Err(e) => eprintln!("Failed to create task: {}", e),
+            let client = primary_client(&config)?;
+            if let Err(e) = command::create_task(&client, &content).await {
+                eprintln!("Failed to create task: {}", e);
+                std::process::exit(1);
+            }
+            println!("Task created.");
+            Ok(())
+        }
+        Some(Commands::Api {
+            command: ApiCommand::Check,
+        }) => {
+            let client = primary_client(&config)?;
+            println!("Checking {}", client.base_url());
+            let reports = contract::check(&client).await;
+            for report in &reports {
+                let mark = if !report.is_ok() {
+                    "✗"
+                } else if !report.unknown.is_empty() {
+                    "!"
+                } else {
+                    "✓"
+                };
+                println!("{} {} ({} items)", mark, report.endpoint, report.items);
+                if let Some(error) = &report.error {
+                    println!("    error: {}", error);
                 }
-            } else {
-                eprintln!("No human agent found (required for identity).");
+                if !report.missing.is_empty() {
+                    println!("    missing: {}", join(&report.missing));
+                }
+                if !report.unknown.is_empty() {
+                    println!("    unknown: {}", join(&report.unknown));
+                }
+            }
+            if reports.iter().any(|r| !r.is_ok()) {
                 std::process::exit(1);
             }
             Ok(())
         }
-        None => run_tui().await,
+        Some(Commands::Config {
+            command: ConfigCommand::Show,
+        }) => {
+            match &config_path {
+                Some(path) if path.exists() => println!("# {}", path.display()),
+                Some(path) => println!("# {} (not found, defaults)", path.display()),
+                None => println!("# defaults"),
+            }
+            print!("{}", toml::to_string_pretty(&config.effective())?);
+            Ok(())
+        }
+        #[cfg(feature = "mock")]
+        Some(Commands::MockServer { scenario, port }) => {
+            let scenario = match scenario {
+                Some(path) => mock::Scenario::load(&path)?,
+                None => mock::Scenario::default(),
+            };
+            let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+            println!(
+                "mock space-os listening on http://{}",
+                listener.local_addr()?
+            );
+            mock::serve(listener, scenario).await?;
+            Ok(())
+        }
+        Some(Commands::Record { file }) => {
+            let recorder = Recorder::create(&file)?;
+            match &cli.fixture {
+                Some(path) => {
+                    run_tui(with_recorder(fixture_instances(path)?, recorder), &config).await
+                }
+                None => run_tui(with_recorder(api_instances(&config)?, recorder), &config).await,
+            }
+        }
+        Some(Commands::Replay { file }) => {
+            let Some(replay) = Replay::new(record::load(&file)?) else {
+                eprintln!("{}: no recorded entries", file.display());
+                std::process::exit(1);
+            };
+            run_replay(replay, &config).await
+        }
+        None => match &cli.fixture {
+            Some(path) => run_tui(fixture_instances(path)?, &config).await,
+            None => run_tui(api_instances(&config)?, &config).await,
+        },
     }
 }
 
-async fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
-    let src = Source::connect();
+fn join(fields: &std::collections::BTreeSet<String>) -> String {
+    fields.iter().cloned().collect::<Vec<_>>().join(", ")
+}
+
+type Tui = Terminal<CrosstermBackend<io::Stdout>>;
 
+fn enter_terminal() -> io::Result<Tui> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-
-    let mut app_state = AppState::new();
-
-    // Initial fetch
-    let (agents, spawns, identities) = tokio::join!(
-        src.get_agents(),
-        src.get_spawns(),
-        src.get_agent_identities()
-    );
-    app_state.agents = agents;
-    app_state.spawns = spawns;
-    app_state.agent_identities = identities;
-
-    app_state.activity = if let Some(agent) = app_state.active_agent() {
-        src.get_agent_activity(&agent.id, 500).await
-    } else {
-        vec![]
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    // Lets terminals that support it report Shift+Enter apart from Enter.
+    if supports_keyboard_enhancement().unwrap_or(false) {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
+    Terminal::new(CrosstermBackend::new(stdout))
+}
+
+fn leave_terminal(terminal: &mut Tui) -> io::Result<()> {
+    // Terminals without the keyboard protocol ignore the pop.
+    execute!(
+        terminal.backend_mut(),
+        PopKeyboardEnhancementFlags,
+        DisableBracketedPaste
+    )?;
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)
+}
+
+// Forwards one instance's updates into a shared channel, tagged with its index.
+fn tagged<T: Send + 'static>(
+    instance: usize,
+    shared: &mpsc::UnboundedSender<(usize, T)>,
+) -> mpsc::UnboundedSender<T> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let shared = shared.clone();
+    tokio::spawn(async move {
+        while let Some(item) = rx.recv().await {
+            if shared.send((instance, item)).is_err() {
+                break;
+            }
+        }
+    });
+    tx
+}
+
+// An explicit `--config` must exist; the default location is optional.
+fn load_config(cli: &Cli) -> io::Result<(Option<std::path::PathBuf>, Config)> {
+    if let Some(path) = &cli.config
+        && !path.exists()
+    {
+        return Err(io::Error::other(format!("{}: not found", path.display())));
+    }
+    let path = cli.config.clone().or_else(config::default_path);
+    let config = match &path {
+        Some(path) => Config::load_with(path, &cli.overrides)?,
+        None => Config::parse_with("", &cli.overrides)?,
+    };
+    Ok((path, config))
+}
+
+// One-shot commands talk to the first configured instance.
+fn primary_client(config: &Config) -> io::Result<ApiClient> {
+    ApiClient::connect(&config.instances()[0])
+}
+
+fn api_instances(config: &Config) -> io::Result<Vec<(Instance, Source)>> {
+    config
+        .instances()
+        .into_iter()
+        .map(|instance| {
+            let src = Source::connect(&instance)?;
+            Ok((instance, src))
+        })
+        .collect()
+}
+
+fn fixture_instances(path: &std::path::Path) -> io::Result<Vec<(Instance, Source<FixtureSource>)>> {
+    let instance = Instance::new("fixture", space_cmd::api::api_base_url());
+    Ok(vec![(instance, Source::new(FixtureSource::load(path)?))])
+}
+
+// Records are not tagged by instance, so only the first one is recorded.
+fn with_recorder<D: DataSource>(
+    mut instances: Vec<(Instance, Source<D>)>,
+    recorder: Recorder,
+) -> Vec<(Instance, Source<D>)> {
+    if !instances.is_empty() {
+        let (instance, src) = instances.remove(0);
+        instances.insert(0, (instance, src.with_recorder(recorder)));
+    }
+    instances
+}
+
+async fn run_tui<D: DataSource + 'static>(
+    instances: Vec<(Instance, Source<D>)>,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let clients = instances
+        .iter()
+        .map(|(instance, _)| ApiClient::connect(instance))
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut terminal = enter_terminal()?;
+
+    let mut app_state = AppState::with_config(config);
+    app_state.open_history(history::default_path());
+    app_state.set_instances(instances.iter().map(|(i, _)| i.name.clone()).collect());
+    let sources: Vec<Arc<Source<D>>> = instances
+        .into_iter()
+        .map(|(_, src)| Arc::new(src))
+        .collect();
+
+    // Show the last-known state immediately; the pollers refresh it from the API.
+    if let Some(snapshot) = sources.first().and_then(|src| src.load_cache()) {
+        snapshot.restore(&mut app_state);
+    }
+    terminal.draw(|frame| {
+        render_ui(frame, &app_state);
+    })?;
+
+    // Fetching runs in the background so keys never wait on the network.
+    let mut resync = 1;
+    let (fetched_tx, mut fetched_rx) = mpsc::unbounded_channel::<(usize, Record)>();
+    let (live_tx, mut live_rx) = mpsc::unbounded_channel::<(usize, LiveUpdate)>();
+    let mut focus_txs = Vec::new();
+    let settings = poll::Settings {
+        refresh: config.refresh,
+        fetch_limit: config.limits.fetch,
+    };
+    for (i, src) in sources.iter().enumerate() {
+        let (focus_tx, focus_rx) = watch::channel(Focus {
+            resync,
+            ..app_state.focus_for(i)
+        });
+        poll::spawn(src.clone(), focus_rx, tagged(i, &fetched_tx), settings);
+        tokio::spawn(live::run(clients[i].clone(), tagged(i, &live_tx)));
+        focus_txs.push(focus_tx);
+    }
+
+    let (outcome_tx, mut outcome_rx) = mpsc::unbounded_channel::<CommandResult>();
+    let dispatch = Dispatch {
+        outcome_tx,
+        clients,
     };
-    app_state.ledger = src.get_ledger_activity(500).await;
-    app_state.stream = src.get_tail(200).await;
 
     let mut reader = EventStream::new();
-    let mut interval = tokio::time::interval(Duration::from_millis(500));
+    let mut ticker = tokio::time::interval(REDRAW_INTERVAL);
+    let mut cached = Instant::now();
+    let mut saving: Option<tokio::task::JoinHandle<()>> = None;
 
     loop {
-        let mut should_fetch = false;
         let mut event_received = None;
+        let mut live_update = None;
+        let mut dirty = false;
 
         tokio::select! {
-            _ = interval.tick() => {
-                should_fetch = true;
+            // Ages and spinners still need a periodic redraw when nothing else changed.
+            _ = ticker.tick() => {
+                app_state.expire_daemon_pending();
+                dirty = true;
             }
             Some(Ok(event)) = reader.next() => {
                 event_received = Some(event);
             }
+            Some(update) = live_rx.recv() => {
+                live_update = Some(update);
+            }
+            Some((instance, record)) = fetched_rx.recv() => {
+                dirty = !app_state.paused && app_state.apply_instance_record(instance, &record);
+            }
+            Some((cmd, outcome)) = outcome_rx.recv() => {
+                app_state.finish_command(cmd.as_ref(), outcome);
+                dirty = true;
+            }
         }
 
-        if let Some(Event::Key(key)) = event_received {
-            match key.code {
-                KeyCode::Char('q') => break,
-                KeyCode::Char('h') => app_state.switch_tab(),
-                KeyCode::Char('l') => app_state.switch_tab(),
-                KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    app_state.next_spawn_global();
-                }
-                KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    app_state.prev_spawn_global();
-                }
-                KeyCode::Char('j') => {
-                    app_state.next_in_sidebar();
-                    if !app_state.all_stream {
-                        app_state.activity_scroll_offset = 0;
-                    }
-                }
-                KeyCode::Char('k') => {
-                    app_state.prev_in_sidebar();
-                    if !app_state.all_stream {
-                        app_state.activity_scroll_offset = 0;
-                    }
-                }
-                KeyCode::Char('J') => handle_scroll_down(&mut app_state),
-                KeyCode::Char('K') => handle_scroll_up(&mut app_state),
-                KeyCode::Char(' ') => app_state.toggle_pause(),
-                KeyCode::Char('a') => app_state.toggle_all_stream(),
-                KeyCode::Char('d') => app_state.toggle_right_pane(),
-                KeyCode::Char('e') => app_state.toggle_spawn_expansion(),
-                KeyCode::Char(ch) if key.modifiers.contains(KeyModifiers::ALT) => {
-                    app_state.focus_agent_by_initial(ch);
-                }
-                KeyCode::Char(ch) => {
-                    app_state.add_char(ch);
-                    app_state.detect_and_trigger_autocomplete();
-                }
-                KeyCode::Backspace => {
-                    app_state.backspace();
-                    if app_state.autocomplete_mode.is_some() {
-                        app_state.detect_and_trigger_autocomplete();
-                    }
-                }
-                KeyCode::Enter => {
-                    if app_state.autocomplete_mode.is_some() {
-                        app_state.autocomplete_select();
-                    } else {
-                        let _ = app_state.submit_input(); // This calls create_task which needs to be async or handled
-                    }
-                }
-                KeyCode::Up => {
-                    if app_state.autocomplete_mode.is_some() {
-                        app_state.autocomplete_prev();
-                    } else {
-                        app_state.history_prev();
-                    }
+        dirty |= event_received.is_some() || live_update.is_some();
+        match live_update {
+            Some((instance, LiveUpdate::Connected)) => {
+                app_state.instances[instance].live = true;
+                resync += 1;
+            }
+            Some((instance, LiveUpdate::Disconnected)) => {
+                app_state.instances[instance].live = false;
+            }
+            Some((instance, LiveUpdate::Event(event))) => {
+                sources[instance].record(|| Record::Live {
+                    event: event.clone(),
+                });
+                if !app_state.paused {
+                    app_state.apply_instance_live(instance, *event);
                 }
-                KeyCode::Down => {
-                    if app_state.autocomplete_mode.is_some() {
-                        app_state.autocomplete_next();
-                    } else {
-                        app_state.history_next();
-                    }
+            }
+            None => {}
+        }
+
+        if let Some(Event::Paste(text)) = &event_received {
+            handle_paste(&mut app_state, text);
+        }
+        if let Some(Event::Key(key)) = event_received {
+            let was_paused = app_state.paused;
+            let view = (app_state.instance, app_state.merged);
+            if handle_key(&mut app_state, key, &dispatch) {
+                break;
+            }
+            if (was_paused && !app_state.paused) || view != (app_state.instance, app_state.merged) {
+                resync += 1;
+            }
+        }
+
+        let shown = app_state.shown_instance();
+        if !app_state.paused
+            && let Some(query) = app_state.take_older_activity_query()
+        {
+            let src = sources[shown].clone();
+            let tx = fetched_tx.clone();
+            tokio::spawn(async move {
+                if let Ok(items) = src.get_activity_page(&query).await {
+                    let _ = tx.send((
+                        shown,
+                        Record::ActivityPage {
+                            agent_id: query.agent_id,
+                            items,
+                        },
+                    ));
                 }
-                KeyCode::Esc => {
-                    if app_state.autocomplete_mode.is_some() {
-                        app_state.cancel_autocomplete();
-                    } else {
-                        app_state.input_text.clear();
-                        app_state.history_idx = None;
-                    }
+            });
+        }
+        for (i, src) in sources.iter().enumerate() {
+            app_state.instances[i].endpoints = src.endpoint_stats();
+        }
+        app_state.endpoints = app_state.instances[shown].endpoints.clone();
+        app_state.live = app_state.instances[shown].live;
+        for (i, focus_tx) in focus_txs.iter().enumerate() {
+            focus_tx.send_if_modified(|focus| {
+                let next = Focus {
+                    resync,
+                    ..app_state.focus_for(i)
+                };
+                let changed = *focus != next;
+                *focus = next;
+                changed
+            });
+        }
+
+        // Serializing and writing the snapshot happens off the UI loop, one save at a time.
+        if cached.elapsed() >= CACHE_INTERVAL && saving.as_ref().is_none_or(|s| s.is_finished()) {
+            saving = cache_snapshot(&sources, &app_state).map(|(src, snapshot)| {
+                tokio::task::spawn_blocking(move || {
+                    let _ = src.save_cache(&snapshot);
+                })
+            });
+            cached = Instant::now();
+        }
+
+        if dirty {
+            terminal.draw(|frame| {
+                render_ui(frame, &app_state);
+            })?;
+        }
+    }
+
+    if let Some(saving) = saving {
+        let _ = saving.await;
+    }
+    if let Some((src, snapshot)) = cache_snapshot(&sources, &app_state) {
+        let _ = src.save_cache(&snapshot);
+    }
+    leave_terminal(&mut terminal)?;
+
+    Ok(())
+}
+
+async fn run_replay(mut replay: Replay, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let mut terminal = enter_terminal()?;
+
+    let mut app_state = AppState::with_config(config);
+    app_state.open_history(history::default_path());
+    replay.advance(Duration::ZERO, &mut app_state);
+
+    // Commands are refused in replay, but handle_key still needs somewhere to send outcomes.
+    let (outcome_tx, mut outcome_rx) = mpsc::unbounded_channel::<CommandResult>();
+    let dispatch = Dispatch {
+        outcome_tx,
+        clients: Vec::new(),
+    };
+    let mut reader = EventStream::new();
+    let mut interval = tokio::time::interval(Duration::from_millis(100));
+    let mut last_tick = Instant::now();
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                replay.advance(last_tick.elapsed(), &mut app_state);
+                last_tick = Instant::now();
+            }
+            Some(Ok(event)) = reader.next() => match event {
+                Event::Key(key)
+                    if !handle_replay_key(&mut replay, &mut app_state, key)
+                        && handle_key(&mut app_state, key, &dispatch) =>
+                {
+                    break;
                 }
+                Event::Paste(text) => handle_paste(&mut app_state, &text),
                 _ => {}
+            },
+            Some((cmd, outcome)) = outcome_rx.recv() => {
+                app_state.finish_command(cmd.as_ref(), outcome);
             }
         }
 
-        if should_fetch && !app_state.paused {
-            // Parallel fetch using join!
-            let (agents, spawns, identities) = tokio::join!(
-                src.get_agents(),
-                src.get_spawns(),
-                src.get_agent_identities()
-            );
+        terminal.draw(|frame| {
+            render_ui(frame, &app_state);
+        })?;
+    }
 
-            app_state.agents = agents;
-            app_state.spawns = spawns;
-            app_state.agent_identities = identities;
+    leave_terminal(&mut terminal)?;
+    Ok(())
+}
 
-            // Dependent fetches
-            app_state.activity = if app_state.all_stream {
-                src.get_activity(500).await
-            } else if let Some(agent) = app_state.active_agent() {
-                src.get_agent_activity(&agent.id, 500).await
+fn handle_replay_key(replay: &mut Replay, app_state: &mut AppState, key: KeyEvent) -> bool {
+    let idle = !app_state.is_inserting()
+        && !app_state.help
+        && app_state.confirm.is_none()
+        && app_state.transcript.is_none();
+    if !idle {
+        return false;
+    }
+    match key.code {
+        KeyCode::Char(' ') => replay.toggle_play(),
+        KeyCode::Char('[') => replay.slower(),
+        KeyCode::Char(']') => replay.faster(),
+        KeyCode::Left => replay.seek(-10, app_state),
+        KeyCode::Right => replay.seek(10, app_state),
+        KeyCode::Char('{') => replay.seek(-60, app_state),
+        KeyCode::Char('}') => replay.seek(60, app_state),
+        _ => return false,
+    }
+    app_state.replay = Some(replay.status());
+    true
+}
+
+fn cache_snapshot<D: DataSource>(
+    sources: &[Arc<Source<D>>],
+    app_state: &AppState,
+) -> Option<(Arc<Source<D>>, Snapshot)> {
+    // The merged view mixes instances, so only a single instance's view is cached.
+    if app_state.merged {
+        return None;
+    }
+    let src = sources.get(app_state.instance)?;
+    // Never overwrite a good cache with a snapshot that was only ever loaded from it.
+    src.has_fetched()
+        .then(|| (src.clone(), Snapshot::capture(app_state)))
+}
+
+fn handle_key(app_state: &mut AppState, key: KeyEvent, dispatch: &Dispatch) -> bool {
+    if app_state.confirm.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                if let Some(cmd) = app_state.take_confirmed() {
+                    dispatch_command(app_state, cmd, dispatch);
+                }
+            }
+            _ => app_state.cancel_confirm(),
+        }
+    } else if app_state.help {
+        // Any key closes the overlay.
+        app_state.toggle_help();
+    } else if app_state.transcript.is_some() {
+        handle_transcript_key(app_state, key);
+    } else if app_state.is_inserting() {
+        handle_insert_key(app_state, key, dispatch);
+    } else if app_state.global_search.as_ref().is_some_and(|s| s.editing) {
+        handle_global_search_key(app_state, key);
+    } else if let Some(action) = app_state.keymap.action(&key) {
+        return run_action(app_state, action, dispatch);
+    } else if let KeyCode::Char(ch) = key.code
+        && key.modifiers.contains(KeyModifiers::ALT)
+    {
+        app_state.focus_agent_by_initial(ch);
+    }
+    false
+}
+
+// Returns true when the action quits.
+fn run_action(app_state: &mut AppState, action: Action, dispatch: &Dispatch) -> bool {
+    match action {
+        Action::Quit => return true,
+        Action::Help => app_state.toggle_help(),
+        Action::Insert => app_state.start_input(None),
+        Action::Command => app_state.start_input(Some(':')),
+        Action::Mention => app_state.start_input(Some('@')),
+        Action::File => app_state.start_input(Some('/')),
+        Action::HistorySearch => {
+            app_state.start_input(None);
+            app_state.start_history_search();
+        }
+        Action::Search => app_state.global_search_start(),
+        Action::SearchNext => app_state.global_search_next(),
+        Action::SearchPrev => app_state.global_search_prev(),
+        Action::ClearInput if app_state.global_search.is_some() => {
+            app_state.global_search_cancel();
+        }
+        Action::ClearInput => {
+            app_state.input_text.clear();
+            app_state.input_cursor = None;
+            app_state.history_idx = None;
+        }
+        Action::SwitchTab => app_state.switch_tab(),
+        Action::Down => {
+            app_state.next_in_sidebar();
+            if !app_state.all_stream {
+                app_state.activity_scroll_offset = 0;
+            }
+        }
+        Action::Up => {
+            app_state.prev_in_sidebar();
+            if !app_state.all_stream {
+                app_state.activity_scroll_offset = 0;
+            }
+        }
+        Action::NextSpawn => app_state.next_spawn_global(),
+        Action::PrevSpawn => app_state.prev_spawn_global(),
+        Action::ScrollPaneDown => handle_scroll_down(app_state),
+        Action::ScrollPaneUp => handle_scroll_up(app_state),
+        Action::ScrollActivityDown => app_state.scroll_activity_down(),
+        Action::ScrollActivityUp => app_state.scroll_activity_up(),
+        Action::Transcript => {
+            if !app_state.open_transcript() {
+                app_state.show_toast(ToastKind::Error, "no spawn to open");
+            }
+        }
+        Action::Pause => app_state.toggle_pause(),
+        Action::AllStream => app_state.toggle_all_stream(),
+        Action::CyclePane => app_state.toggle_right_pane(),
+        Action::Expand => app_state.toggle_spawn_expansion(),
+        Action::Tree => app_state.toggle_spawn_tree(),
+        Action::Collapse => app_state.toggle_spawn_collapse(),
+        Action::Kill => app_state.request_kill(),
+        Action::Retry => {
+            if let Some(cmd) = app_state.request_retry() {
+                dispatch_command(app_state, cmd, dispatch);
+            }
+        }
+        Action::FollowUp => app_state.toggle_follow_up(),
+        Action::Swarm => {
+            let cmd = app_state.swarm_toggle_command();
+            dispatch_command(app_state, cmd, dispatch);
+        }
+        Action::ConcurrencyUp | Action::ConcurrencyDown => {
+            let delta = if action == Action::ConcurrencyUp {
+                1
             } else {
-                vec![]
+                -1
             };
-
-            if let Some(spawn) = app_state.selected_spawn() {
-                app_state.spawn_activity = src.get_spawn_activity(&spawn.id, 200).await;
+            if let Some(cmd) = app_state.concurrency_command(delta) {
+                dispatch_command(app_state, cmd, dispatch);
+            }
+        }
+        Action::Daemon => {
+            if let Some(cmd) = app_state.request_daemon_toggle() {
+                dispatch_command(app_state, cmd, dispatch);
             }
+        }
+        Action::NextInstance => app_state.next_instance(),
+        Action::Merged => app_state.toggle_merged(),
+    }
+    false
+}
 
-            match app_state.right_pane {
-                RightPane::Stream => {
-                    app_state.stream = if app_state.all_stream {
-                        src.get_tail(200).await
-                    } else if let Some(agent) = app_state.active_agent() {
-                        src.get_agent_tail(&agent.identity, 200).await
-                    } else {
-                        src.get_tail(200).await
-                    };
-                }
-                RightPane::Ledger => {
-                    app_state.ledger = src.get_ledger_activity(500).await;
+// A paste goes into the search query while one is typed, else into the input
+// line, whichever mode we're in.
+fn handle_paste(app_state: &mut AppState, text: &str) {
+    if app_state.confirm.is_some() || app_state.help || app_state.transcript.is_some() {
+        return;
+    }
+    if app_state.global_search.as_ref().is_some_and(|s| s.editing) {
+        for ch in text.chars().filter(|c| !c.is_control()) {
+            app_state.global_search_push(ch);
+        }
+        return;
+    }
+    app_state.cancel_autocomplete();
+    app_state.start_input(None);
+    app_state.paste(text);
+}
+
+// Re-runs completion after an edit that may have shortened the current word.
+fn refresh_autocomplete(app_state: &mut AppState) {
+    if app_state.autocomplete_mode.is_some() || app_state.is_command_mode() {
+        app_state.detect_and_trigger_autocomplete();
+    }
+}
+
+// Ctrl+R search: typing narrows it, Ctrl+R goes older, Enter keeps the match
+// for editing, Esc/Ctrl+G puts the line back. Any other key keeps the match and
+// is handled as usual.
+fn handle_search_key(app_state: &mut AppState, key: KeyEvent, dispatch: &Dispatch) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Char('r') if ctrl => app_state.search_older(),
+        KeyCode::Char('g') if ctrl => app_state.cancel_history_search(),
+        KeyCode::Esc => app_state.cancel_history_search(),
+        KeyCode::Char(ch) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+            app_state.search_push(ch)
+        }
+        KeyCode::Backspace => app_state.search_backspace(),
+        KeyCode::Enter => app_state.accept_history_search(),
+        _ => {
+            app_state.accept_history_search();
+            handle_insert_key(app_state, key, dispatch);
+        }
+    }
+}
+
+// Insert mode keys are fixed: everything printable is text.
+fn handle_insert_key(app_state: &mut AppState, key: KeyEvent, dispatch: &Dispatch) {
+    if app_state.history_search.is_some() {
+        handle_search_key(app_state, key, dispatch);
+        return;
+    }
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    match key.code {
+        KeyCode::Char('r') if ctrl => app_state.start_history_search(),
+        KeyCode::Char(ch) if !ctrl && !alt => {
+            app_state.add_char(ch);
+            app_state.detect_and_trigger_autocomplete();
+        }
+        // Ctrl+J is a bare line feed in raw mode, for terminals that can't report Shift+Enter.
+        KeyCode::Char('j') if ctrl => app_state.insert_newline(),
+        KeyCode::Enter if alt || key.modifiers.contains(KeyModifiers::SHIFT) => {
+            app_state.cancel_autocomplete();
+            app_state.insert_newline();
+        }
+        KeyCode::Char('w') if ctrl => {
+            app_state.delete_word_back();
+            refresh_autocomplete(app_state);
+        }
+        KeyCode::Backspace if alt => {
+            app_state.delete_word_back();
+            refresh_autocomplete(app_state);
+        }
+        KeyCode::Char('u') if ctrl => {
+            app_state.kill_line_back();
+            refresh_autocomplete(app_state);
+        }
+        KeyCode::Char('d') if ctrl => app_state.delete_forward(),
+        KeyCode::Delete => app_state.delete_forward(),
+        KeyCode::Char('a') if ctrl => app_state.cursor_home(),
+        KeyCode::Char('e') if ctrl => app_state.cursor_end(),
+        KeyCode::Char('b') if alt => {
+            app_state.cancel_autocomplete();
+            app_state.word_left();
+        }
+        KeyCode::Char('f') if alt => {
+            app_state.cancel_autocomplete();
+            app_state.word_right();
+        }
+        KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End => {
+            app_state.cancel_autocomplete();
+            match key.code {
+                KeyCode::Left if ctrl => app_state.word_left(),
+                KeyCode::Right if ctrl => app_state.word_right(),
+                KeyCode::Left => app_state.cursor_left(),
+                KeyCode::Right => app_state.cursor_right(),
+                KeyCode::Home => app_state.cursor_home(),
+                _ => app_state.cursor_end(),
+            }
+        }
+        KeyCode::Backspace => {
+            app_state.backspace();
+            refresh_autocomplete(app_state);
+        }
+        KeyCode::Enter => {
+            if app_state.is_completing() {
+                app_state.autocomplete_select();
+                if app_state.is_command_mode() {
+                    app_state.detect_and_trigger_autocomplete();
                 }
+            } else if app_state.is_command_mode() {
+                submit_command(app_state, dispatch);
+            } else if app_state.input_text.is_empty() {
+                app_state.leave_insert();
+            } else if app_state.replay.is_some() {
+                app_state.show_toast(ToastKind::Error, "replay is read-only");
+            } else if let Some(line) = app_state.submit_input() {
+                app_state.show_toast(ToastKind::Pending, format!("sending: {}", line));
+                let identity = dispatch
+                    .clients
+                    .get(app_state.shown_instance())
+                    .and_then(|c| c.identity())
+                    .map(String::from);
+                let tx = dispatch.outcome_tx.clone();
+                tokio::spawn(async move {
+                    let _ = tx.send((None, bridge::send(&line, identity.as_deref()).await));
+                });
+            }
+        }
+        KeyCode::Up => {
+            if app_state.is_completing() {
+                app_state.autocomplete_prev();
+            } else if !app_state.cursor_up() {
+                app_state.history_prev();
+            }
+        }
+        KeyCode::Down => {
+            if app_state.is_completing() {
+                app_state.autocomplete_next();
+            } else if !app_state.cursor_down() {
+                app_state.history_next();
             }
+        }
+        KeyCode::Esc => {
+            if app_state.autocomplete_mode.is_some() {
+                app_state.cancel_autocomplete();
+            } else {
+                app_state.leave_insert();
+            }
+        }
+        _ => {}
+    }
+}
 
-            let active_count = app_state
-                .spawns
-                .iter()
-                .filter(|s| s.status == "active")
-                .count();
-            app_state.daemon = src.get_daemon_status(active_count).await;
+fn handle_global_search_key(app_state: &mut AppState, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char(ch) => app_state.global_search_push(ch),
+        KeyCode::Backspace => app_state.global_search_pop(),
+        KeyCode::Enter => app_state.global_search_commit(),
+        KeyCode::Esc => app_state.global_search_cancel(),
+        _ => {}
+    }
+}
+
+fn handle_transcript_key(app_state: &mut AppState, key: KeyEvent) {
+    let searching = app_state.transcript.as_ref().is_some_and(|t| t.searching);
+    if searching {
+        match key.code {
+            KeyCode::Char(ch) => app_state.transcript_search_push(ch),
+            KeyCode::Backspace => app_state.transcript_search_pop(),
+            KeyCode::Enter => app_state.transcript_search_commit(),
+            KeyCode::Esc => app_state.transcript_search_cancel(),
+            _ => {}
         }
+        return;
+    }
 
-        terminal.draw(|frame| {
-            render_ui(frame, &app_state);
-        })?;
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app_state.close_transcript(),
+        KeyCode::Char('j') | KeyCode::Down => app_state.transcript_next_event(),
+        KeyCode::Char('k') | KeyCode::Up => app_state.transcript_prev_event(),
+        KeyCode::Char('J') => app_state.transcript_scroll_down(1),
+        KeyCode::Char('K') => app_state.transcript_scroll_up(1),
+        KeyCode::PageDown => app_state.transcript_scroll_down(10),
+        KeyCode::PageUp => app_state.transcript_scroll_up(10),
+        KeyCode::Char('g') | KeyCode::Home => app_state.transcript_first(),
+        KeyCode::Char('G') | KeyCode::End => app_state.transcript_last(),
+        KeyCode::Char('o') | KeyCode::Tab | KeyCode::Enter => app_state.transcript_toggle_fold(),
+        KeyCode::Char('/') => app_state.transcript_search_start(),
+        KeyCode::Char('n') => app_state.transcript_next_match(),
+        KeyCode::Char('N') => app_state.transcript_prev_match(),
+        _ => {}
     }
+}
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+fn submit_command(app_state: &mut AppState, dispatch: &Dispatch) {
+    let cmd = match command::parse(&app_state.input_text) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            app_state.show_toast(ToastKind::Error, e.to_string());
+            return;
+        }
+    };
+    app_state.submit_input();
 
-    Ok(())
+    let cmd = match cmd {
+        Command::Pause => {
+            app_state.toggle_pause();
+            return;
+        }
+        Command::Focus(identity) => {
+            if !app_state.focus_agent(&identity) {
+                app_state.show_toast(ToastKind::Error, format!("no agent @{}", identity));
+            }
+            return;
+        }
+        Command::Kill(ref prefix) | Command::Retry(ref prefix) => {
+            let Some(spawn_id) = app_state.resolve_spawn_id(prefix) else {
+                app_state.show_toast(
+                    ToastKind::Error,
+                    format!("no unique spawn matching {}", prefix),
+                );
+                return;
+            };
+            if let Command::Kill(_) = cmd {
+                app_state.request_kill_spawn(&spawn_id);
+                return;
+            }
+            Command::Retry(spawn_id)
+        }
+        other => other,
+    };
+
+    dispatch_command(app_state, cmd, dispatch);
+}
+
+fn dispatch_command(app_state: &mut AppState, cmd: Command, dispatch: &Dispatch) {
+    if app_state.replay.is_some() {
+        app_state.show_toast(ToastKind::Error, "replay is read-only");
+        return;
+    }
+    let Some(client) = dispatch
+        .clients
+        .get(app_state.command_instance(&cmd))
+        .cloned()
+    else {
+        return;
+    };
+    app_state.begin_daemon_change(&cmd);
+    app_state.show_toast(ToastKind::Pending, "running command");
+    let tx = dispatch.outcome_tx.clone();
+    tokio::spawn(async move {
+        let outcome = command::run(&client, cmd.clone()).await;
+        let _ = tx.send((Some(cmd), outcome));
+    });
 }
@@ -0,0 +1,597 @@
+use crate::schema::{Activity, Agent, DaemonStatus, LedgerQuery, Spawn, TailEntry};
+use crate::source::Fixture;
+use axum::{
+    Json, Router,
+    extract::{
+        Path, Query, Request, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, patch, post},
+};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Scenario {
+    #[serde(flatten)]
+    pub fixture: Fixture,
+    pub latency_ms: u64,
+    pub failures: Vec<Failure>,
+    pub lifecycles: Vec<Lifecycle>,
+    // When set, every request must carry it as a bearer token or `X-API-Key`.
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Failure {
+    pub path: String,
+    #[serde(default = "default_status")]
+    pub status: u16,
+    #[serde(default = "default_every")]
+    pub every: u32,
+    #[serde(default)]
+    pub from_s: u64,
+    #[serde(default)]
+    pub until_s: Option<u64>,
+}
+
+fn default_status() -> u16 {
+    500
+}
+
+fn default_every() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lifecycle {
+    pub spawn_id: String,
+    pub agent_id: String,
+    #[serde(default)]
+    pub caller_spawn_id: Option<String>,
+    #[serde(default)]
+    pub start_s: u64,
+    pub run_s: u64,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub events: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Step {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+impl Scenario {
+    pub fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        let raw = std::fs::read_to_string(path)?;
+        serde_json::from_str(&raw).map_err(std::io::Error::other)
+    }
+}
+
+enum Phase {
+    Pending,
+    Running(usize),
+    Finished,
+}
+
+struct MockState {
+    scenario: Scenario,
+    started: Instant,
+    started_at: DateTime<Utc>,
+    hits: HashMap<String, u32>,
+    killed: HashMap<String, String>,
+}
+
+type Shared = Arc<Mutex<MockState>>;
+
+const LIVE_TICK: Duration = Duration::from_millis(250);
+
+// What one `/ws/events` connection has been told so far.
+#[derive(Default)]
+struct Pushed {
+    spawns: HashMap<String, (String, Option<String>)>,
+    steps: HashMap<String, usize>,
+    ledger: HashSet<String>,
+    daemon: Option<DaemonStatus>,
+}
+
+impl MockState {
+    fn elapsed_s(&self) -> u64 {
+        self.started.elapsed().as_secs()
+    }
+
+    fn at(&self, offset_s: u64) -> String {
+        (self.started_at + ChronoDuration::seconds(offset_s as i64)).to_rfc3339()
+    }
+
+    fn phase(&self, lc: &Lifecycle) -> Phase {
+        if self.killed.contains_key(&lc.spawn_id) {
+            return Phase::Finished;
+        }
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let start = lc.start_s as f64;
+        let run = lc.run_s.max(1) as f64;
+        if elapsed < start {
+            Phase::Pending
+        } else if elapsed < start + run {
+            let shown = (lc.events.len() as f64 * (elapsed - start) / run).ceil();
+            Phase::Running(shown as usize)
+        } else {
+            Phase::Finished
+        }
+    }
+
+    fn failure_for(&mut self, path: &str) -> Option<u16> {
+        let elapsed = self.elapsed_s();
+        let hits = self.hits.entry(path.to_string()).or_insert(0);
+        *hits += 1;
+        let hits = *hits;
+        self.scenario
+            .failures
+            .iter()
+            .filter(|f| path.starts_with(&f.path))
+            .filter(|f| elapsed >= f.from_s && f.until_s.is_none_or(|until| elapsed < until))
+            .find(|f| hits.is_multiple_of(f.every.max(1)))
+            .map(|f| f.status)
+    }
+
+    fn spawns(&self) -> Vec<Spawn> {
+        let mut spawns: Vec<Spawn> = self
+            .scenario
+            .lifecycles
+            .iter()
+            .rev()
+            .filter_map(|lc| {
+                let (status, error) = match self.phase(lc) {
+                    Phase::Pending => return None,
+                    Phase::Running(_) => ("active", None),
+                    Phase::Finished => (
+                        "done",
+                        self.killed.get(&lc.spawn_id).cloned().or(lc.error.clone()),
+                    ),
+                };
+                Some(Spawn {
+                    id: lc.spawn_id.clone(),
+                    agent_id: lc.agent_id.clone(),
+                    project_id: None,
+                    caller_spawn_id: lc.caller_spawn_id.clone(),
+                    source: Some("mock".to_string()),
+                    status: status.to_string(),
+                    error,
+                    pid: None,
+                    session_id: None,
+                    summary: None,
+                    trace_hash: None,
+                    created_at: self.at(lc.start_s),
+                    last_active_at: Some(Utc::now().to_rfc3339()),
+                })
+            })
+            .collect();
+        for spawn in &self.scenario.fixture.spawns {
+            let mut spawn = spawn.clone();
+            if let Some(error) = self.killed.get(&spawn.id) {
+                spawn.status = "done".to_string();
+                spawn.error = Some(error.clone());
+            }
+            spawns.push(spawn);
+        }
+        spawns
+    }
+
+    fn revealed_steps(&self, lc: &Lifecycle) -> usize {
+        match self.phase(lc) {
+            Phase::Pending => 0,
+            Phase::Running(shown) => shown,
+            Phase::Finished => lc.events.len(),
+        }
+    }
+
+    fn daemon(&self) -> DaemonStatus {
+        let mut daemon = self.scenario.fixture.daemon.clone();
+        daemon.active_count = self
+            .spawns()
+            .iter()
+            .filter(|s| s.status == "active")
+            .count();
+        daemon
+    }
+
+    // Live events for everything that changed since `pushed`, which is brought up to date.
+    fn live_events(&self, pushed: &mut Pushed) -> Vec<Value> {
+        let mut events = Vec::new();
+        for spawn in self.spawns() {
+            let state = (spawn.status.clone(), spawn.error.clone());
+            if pushed.spawns.get(&spawn.id) != Some(&state) {
+                pushed.spawns.insert(spawn.id.clone(), state);
+                events.push(json!({ "type": "spawn", "data": spawn }));
+            }
+        }
+        for lc in &self.scenario.lifecycles {
+            let shown = self.revealed_steps(lc);
+            let sent = pushed.steps.insert(lc.spawn_id.clone(), shown).unwrap_or(0);
+            let agent = self.identity(&lc.agent_id);
+            events.extend(lc.events.iter().take(shown).skip(sent).map(|step| {
+                json!({ "type": "tail", "data": {
+                    "spawn": lc.spawn_id[..lc.spawn_id.len().min(8)],
+                    "agent": agent,
+                    "type": step.kind,
+                    "content": step.content,
+                    "name": step.name,
+                } })
+            }));
+        }
+        for act in self.scenario.fixture.ledger.iter().rev() {
+            if pushed.ledger.insert(act.primitive_id.clone()) {
+                events.push(json!({ "type": "ledger", "data": activity_to_ledger(act) }));
+            }
+        }
+        let daemon = self.daemon();
+        if pushed.daemon.as_ref() != Some(&daemon) {
+            events.push(json!({ "type": "daemon", "data": daemon }));
+            pushed.daemon = Some(daemon);
+        }
+        events
+    }
+
+    fn identity(&self, agent_id: &str) -> String {
+        self.scenario
+            .fixture
+            .agents
+            .iter()
+            .find(|a| a.id == agent_id)
+            .map(|a| a.identity.clone())
+            .unwrap_or_else(|| agent_id.to_string())
+    }
+
+    fn tail(&self) -> Vec<TailEntry> {
+        let mut tail = self.scenario.fixture.tail.clone();
+        for lc in &self.scenario.lifecycles {
+            let agent = self.identity(&lc.agent_id);
+            tail.extend(
+                lc.events
+                    .iter()
+                    .take(self.revealed_steps(lc))
+                    .map(|step| TailEntry {
+                        spawn: lc.spawn_id[..lc.spawn_id.len().min(8)].to_string(),
+                        agent: agent.clone(),
+                        entry_type: step.kind.clone(),
+                        content: step.content.clone(),
+                        name: step.name.clone(),
+                        args: None,
+                        ctx_pct: None,
+                    }),
+            );
+        }
+        tail
+    }
+}
+
+fn agent_to_value(agent: &Agent) -> Value {
+    json!({
+        "id": agent.id,
+        "identity": agent.identity,
+        "type": agent.agent_type,
+        "model": agent.model,
+        "constitution": agent.constitution,
+        "avatar_path": agent.avatar_path,
+        "color": agent.color,
+        "created_at": agent.created_at,
+        "archived_at": agent.archived_at,
+    })
+}
+
+fn activity_to_ledger(act: &Activity) -> Value {
+    json!({
+        "id": act.primitive_id,
+        "agent_id": act.agent_id,
+        "type": act.primitive,
+        "status": act.action,
+        "content": act.after,
+        "created_at": act.created_at,
+    })
+}
+
+fn activity_to_event(act: &Activity) -> Value {
+    json!({
+        "agent_id": act.agent_id,
+        "type": act.primitive,
+        "name": act.field,
+        "content": act.after,
+        "timestamp": act.created_at,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    limit: Option<usize>,
+    agent: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LedgerParams {
+    limit: Option<usize>,
+    agent_id: Option<String>,
+    #[serde(rename = "type")]
+    primitive: Option<String>,
+    since: Option<String>,
+    before: Option<String>,
+}
+
+fn authorized(token: &str, headers: &axum::http::HeaderMap) -> bool {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    header("authorization").and_then(|v| v.strip_prefix("Bearer ")) == Some(token)
+        || header("x-api-key") == Some(token)
+}
+
+async fn inject(State(state): State<Shared>, req: Request, next: Next) -> Response {
+    let (latency, failure, token) = {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        let failure = state.failure_for(req.uri().path());
+        (
+            state.scenario.latency_ms,
+            failure,
+            state.scenario.token.clone(),
+        )
+    };
+    if latency > 0 {
+        tokio::time::sleep(Duration::from_millis(latency)).await;
+    }
+    if let Some(token) = token
+        && !authorized(&token, req.headers())
+    {
+        return (StatusCode::UNAUTHORIZED, "missing or invalid token").into_response();
+    }
+    match failure {
+        Some(status) => {
+            let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            (status, "scripted failure").into_response()
+        }
+        None => next.run(req).await,
+    }
+}
+
+async fn health(State(state): State<Shared>) -> Json<Value> {
+    let state = state.lock().unwrap_or_else(|e| e.into_inner());
+    match &state.scenario.fixture.health {
+        Value::Null => Json(json!({ "status": "ok", "database": { "connected": true } })),
+        health => Json(health.clone()),
+    }
+}
+
+async fn agents(State(state): State<Shared>) -> Json<Vec<Value>> {
+    let state = state.lock().unwrap_or_else(|e| e.into_inner());
+    Json(
+        state
+            .scenario
+            .fixture
+            .agents
+            .iter()
+            .map(agent_to_value)
+            .collect(),
+    )
+}
+
+async fn spawns(State(state): State<Shared>) -> Json<Vec<Spawn>> {
+    let state = state.lock().unwrap_or_else(|e| e.into_inner());
+    Json(state.spawns())
+}
+
+async fn ledger(State(state): State<Shared>, Query(q): Query<LedgerParams>) -> Json<Vec<Value>> {
+    let state = state.lock().unwrap_or_else(|e| e.into_inner());
+    let query = LedgerQuery {
+        agent_id: q.agent_id,
+        primitive: q.primitive,
+        since: q.since,
+        before: q.before,
+        limit: q.limit.unwrap_or(usize::MAX),
+    };
+    Json(
+        state
+            .scenario
+            .fixture
+            .ledger
+            .iter()
+            .filter(|act| query.matches(act))
+            .take(query.limit)
+            .map(activity_to_ledger)
+            .collect(),
+    )
+}
+
+async fn spawn_events(
+    State(state): State<Shared>,
+    Path(id): Path<String>,
+    Query(q): Query<ListQuery>,
+) -> Json<Value> {
+    let state = state.lock().unwrap_or_else(|e| e.into_inner());
+    let mut events: Vec<Value> = state
+        .scenario
+        .fixture
+        .spawn_events
+        .get(&id)
+        .map(|events| events.iter().map(activity_to_event).collect())
+        .unwrap_or_default();
+    if let Some(lc) = state
+        .scenario
+        .lifecycles
+        .iter()
+        .find(|lc| lc.spawn_id == id)
+    {
+        let shown = state.revealed_steps(lc);
+        events.extend(lc.events.iter().take(shown).enumerate().map(|(i, step)| {
+            json!({
+                "agent_id": lc.agent_id,
+                "type": step.kind,
+                "name": step.name,
+                "content": step.content,
+                "timestamp": state.at(lc.start_s + i as u64),
+            })
+        }));
+    }
+    let limit = q.limit.unwrap_or(usize::MAX);
+    let skip = events.len().saturating_sub(limit);
+    Json(json!({ "events": events.split_off(skip) }))
+}
+
+async fn daemon(State(state): State<Shared>) -> Json<Value> {
+    let state = state.lock().unwrap_or_else(|e| e.into_inner());
+    Json(json!(state.daemon()))
+}
+
+async fn update_daemon(State(state): State<Shared>, Json(body): Json<Value>) -> Json<Value> {
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    let daemon = &mut state.scenario.fixture.daemon;
+    if let Some(enabled) = body["enabled"].as_bool() {
+        daemon.enabled = enabled;
+    }
+    if let Some(concurrency) = body["concurrency"].as_i64() {
+        daemon.concurrency = concurrency as i32;
+    }
+    Json(json!(daemon))
+}
+
+async fn daemon_action(State(state): State<Shared>, Path(action): Path<String>) -> Response {
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    match action.as_str() {
+        "start" => state.scenario.fixture.daemon.running = true,
+        "stop" => state.scenario.fixture.daemon.running = false,
+        _ => return StatusCode::NOT_FOUND.into_response(),
+    }
+    Json(json!(state.scenario.fixture.daemon)).into_response()
+}
+
+async fn events(State(state): State<Shared>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| push_events(socket, state))
+}
+
+// The client refetches everything on connect, so only later changes are pushed.
+async fn push_events(mut socket: WebSocket, state: Shared) {
+    let mut pushed = Pushed::default();
+    state
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .live_events(&mut pushed);
+    let mut tick = tokio::time::interval(LIVE_TICK);
+    loop {
+        tick.tick().await;
+        let events = state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .live_events(&mut pushed);
+        for event in events {
+            if socket
+                .send(Message::Text(event.to_string().into()))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+async fn tail(State(state): State<Shared>, Query(q): Query<ListQuery>) -> Json<Vec<TailEntry>> {
+    let state = state.lock().unwrap_or_else(|e| e.into_inner());
+    let mut tail: Vec<TailEntry> = state
+        .tail()
+        .into_iter()
+        .filter(|e| q.agent.as_ref().is_none_or(|agent| &e.agent == agent))
+        .collect();
+    let skip = tail.len().saturating_sub(q.limit.unwrap_or(usize::MAX));
+    Json(tail.split_off(skip))
+}
+
+async fn create_task(
+    State(state): State<Shared>,
+    headers: axum::http::HeaderMap,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    let creator = headers
+        .get("SPACE_IDENTITY")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    let id = format!("task-{}", state.scenario.fixture.ledger.len() + 1);
+    let task = Activity {
+        id: None,
+        agent_id: creator,
+        spawn_id: None,
+        primitive: "task".to_string(),
+        primitive_id: id.clone(),
+        action: "created".to_string(),
+        field: None,
+        after: body["content"].as_str().map(String::from),
+        created_at: Utc::now().to_rfc3339(),
+    };
+    state.scenario.fixture.ledger.insert(0, task);
+    Json(json!({ "id": id }))
+}
+
+async fn kill_spawn(State(state): State<Shared>, Path(id): Path<String>) -> Response {
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    if !state
+        .spawns()
+        .iter()
+        .any(|s| s.id == id && s.status == "active")
+    {
+        return (StatusCode::CONFLICT, "spawn not active").into_response();
+    }
+    state.killed.insert(id, "killed".to_string());
+    Json(json!({ "ok": true })).into_response()
+}
+
+async fn retry_spawn(State(state): State<Shared>, Path(id): Path<String>) -> Response {
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    if !state.spawns().iter().any(|s| s.id == id) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    state.killed.remove(&id);
+    Json(json!({ "ok": true })).into_response()
+}
+
+pub fn router(scenario: Scenario) -> Router {
+    let state: Shared = Arc::new(Mutex::new(MockState {
+        scenario,
+        started: Instant::now(),
+        started_at: Utc::now(),
+        hits: HashMap::new(),
+        killed: HashMap::new(),
+    }));
+
+    Router::new()
+        .route("/api/health", get(health))
+        .route("/api/agents", get(agents))
+        .route("/api/spawns", get(spawns))
+        .route("/api/spawns/{id}/events", get(spawn_events))
+        .route("/api/spawns/{id}/kill", post(kill_spawn))
+        .route("/api/spawns/{id}/retry", post(retry_spawn))
+        .route("/api/ledger", get(ledger))
+        .route("/api/swarm/daemon", get(daemon).merge(patch(update_daemon)))
+        .route("/api/swarm/daemon/{action}", post(daemon_action))
+        .route("/api/swarm/tail", get(tail))
+        .route("/api/tasks", post(create_task))
+        .route("/ws/events", get(events))
+        .layer(middleware::from_fn_with_state(state.clone(), inject))
+        .with_state(state)
+}
+
+pub async fn serve(listener: TcpListener, scenario: Scenario) -> std::io::Result<()> {
+    axum::serve(listener, router(scenario)).await
+}
@@ -0,0 +1,266 @@
+use crate::config::{Limits, Refresh};
+use crate::record::Record;
+use crate::schema::LedgerQuery;
+use crate::source::{DataSource, Source};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch};
+
+// What the UI is looking at; published by the render loop, read by the poller.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Focus {
+    pub paused: bool,
+    pub live: bool,
+    // Bumped to force a full refetch of everything (WS reconnect, unpause).
+    pub resync: u64,
+    // Bumped after each daemon command so its effect is fetched even while live.
+    pub daemon_check: u64,
+    pub activity: Option<LedgerQuery>,
+    // `Some(None)` is the all-agents tail; `None` means the stream pane is hidden.
+    pub tail: Option<Option<String>>,
+    pub ledger: Option<LedgerQuery>,
+    pub spawn_id: Option<String>,
+}
+
+// Rates and request sizes, fixed for the life of the poller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub refresh: Refresh,
+    pub fetch_limit: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            refresh: Refresh::default(),
+            fetch_limit: Limits::default().fetch,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Roster,
+    Activity,
+    Stream,
+    Ledger,
+    SpawnEvents,
+    Daemon,
+}
+
+impl Resource {
+    pub const ALL: [Resource; 6] = [
+        Resource::Roster,
+        Resource::Activity,
+        Resource::Stream,
+        Resource::Ledger,
+        Resource::SpawnEvents,
+        Resource::Daemon,
+    ];
+
+    pub fn period(self, refresh: &Refresh) -> Duration {
+        Duration::from_millis(match self {
+            Resource::Stream => refresh.stream_ms,
+            Resource::Activity | Resource::Ledger | Resource::SpawnEvents => refresh.activity_ms,
+            Resource::Roster | Resource::Daemon => refresh.roster_ms,
+        })
+    }
+
+    // The part of the focus a fetch depends on; when it changes the request is dropped.
+    pub fn scope(self, focus: &Focus) -> Option<Option<String>> {
+        match self {
+            Resource::Roster | Resource::Daemon => Some(None),
+            Resource::Activity => focus.activity.as_ref().map(|q| q.agent_id.clone()),
+            Resource::Stream => focus.tail.clone(),
+            Resource::Ledger => focus.ledger.as_ref().map(|_| None),
+            Resource::SpawnEvents => focus.spawn_id.clone().map(Some),
+        }
+    }
+
+    async fn fetch<D: DataSource>(
+        self,
+        src: &Source<D>,
+        focus: &Focus,
+        full: bool,
+        limit: usize,
+    ) -> Vec<Record> {
+        let window = |query: &LedgerQuery| LedgerQuery {
+            since: if full { None } else { query.since.clone() },
+            ..query.clone()
+        };
+        match self {
+            Resource::Roster => {
+                let (agents, spawns) = tokio::join!(src.get_agents(), src.get_spawns());
+                let mut records = Vec::new();
+                if let Ok(agents) = agents {
+                    records.push(Record::Agents { agents });
+                }
+                if let Ok(spawns) = spawns {
+                    records.push(Record::Spawns { spawns });
+                }
+                records
+            }
+            Resource::Activity => {
+                let Some(query) = focus.activity.as_ref().map(window) else {
+                    return Vec::new();
+                };
+                src.get_activity(&query)
+                    .await
+                    .map(|items| Record::Activity {
+                        agent_id: query.agent_id.clone(),
+                        delta: query.since.is_some(),
+                        items,
+                    })
+                    .into_iter()
+                    .collect()
+            }
+            Resource::Stream => {
+                let Some(agent) = focus.tail.clone() else {
+                    return Vec::new();
+                };
+                let entries = match &agent {
+                    Some(identity) => src.get_agent_tail(identity, limit).await,
+                    None => src.get_tail(limit).await,
+                };
+                entries
+                    .map(|entries| Record::Tail { agent, entries })
+                    .into_iter()
+                    .collect()
+            }
+            Resource::Ledger => {
+                let Some(query) = focus.ledger.as_ref().map(window) else {
+                    return Vec::new();
+                };
+                src.get_ledger_activity(&query)
+                    .await
+                    .map(|items| Record::Ledger {
+                        delta: query.since.is_some(),
+                        items,
+                    })
+                    .into_iter()
+                    .collect()
+            }
+            Resource::SpawnEvents => {
+                let Some(spawn_id) = focus.spawn_id.clone() else {
+                    return Vec::new();
+                };
+                src.get_spawn_activity(&spawn_id, limit)
+                    .await
+                    .map(|items| Record::SpawnEvents { spawn_id, items })
+                    .into_iter()
+                    .collect()
+            }
+            Resource::Daemon => src
+                .get_daemon_status()
+                .await
+                .map(|status| Record::Daemon { status })
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+// Spawns one polling task per resource. Tasks stop once the UI drops `tx` or the focus sender.
+pub fn spawn<D: DataSource + 'static>(
+    src: Arc<Source<D>>,
+    focus: watch::Receiver<Focus>,
+    tx: mpsc::UnboundedSender<Record>,
+    settings: Settings,
+) {
+    for resource in Resource::ALL {
+        tokio::spawn(run(
+            resource,
+            src.clone(),
+            focus.clone(),
+            tx.clone(),
+            settings,
+        ));
+    }
+}
+
+async fn run<D: DataSource>(
+    resource: Resource,
+    src: Arc<Source<D>>,
+    mut focus_rx: watch::Receiver<Focus>,
+    tx: mpsc::UnboundedSender<Record>,
+    settings: Settings,
+) {
+    let mut resync = 0;
+    let mut daemon_check = 0;
+    let mut full_at: Option<Instant> = None;
+    // The scope of the last completed fetch; a new agent or pane needs its own snapshot.
+    let mut fetched = None;
+
+    loop {
+        let focus = focus_rx.borrow_and_update().clone();
+        let resynced = focus.resync != resync;
+        resync = focus.resync;
+        let rechecked = resource == Resource::Daemon && focus.daemon_check != daemon_check;
+        daemon_check = focus.daemon_check;
+        let scope = resource.scope(&focus);
+        let rescoped = fetched.as_ref() != Some(&scope);
+
+        // While the WS is up it carries everything except spawn events, but only
+        // for what is already shown.
+        let wanted =
+            resynced || rescoped || rechecked || !focus.live || resource == Resource::SpawnEvents;
+        if !focus.paused && wanted {
+            let full = resynced
+                || rescoped
+                || full_at.is_none_or(|at| at.elapsed() >= settings.refresh.full_interval());
+            tokio::select! {
+                records = resource.fetch(&src, &focus, full, settings.fetch_limit) => {
+                    if full {
+                        full_at = Some(Instant::now());
+                    }
+                    fetched = Some(scope);
+                    for record in records {
+                        if tx.send(record).is_err() {
+                            return;
+                        }
+                    }
+                }
+                // The selection moved on: drop the request and fetch for the new one.
+                alive = refocused(&mut focus_rx, resource, &focus) => {
+                    if !alive {
+                        return;
+                    }
+                    continue;
+                }
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(resource.period(&settings.refresh)) => {}
+            alive = refocused(&mut focus_rx, resource, &focus) => {
+                if !alive {
+                    return;
+                }
+            }
+        }
+        if tx.is_closed() {
+            return;
+        }
+    }
+}
+
+// Waits until something the resource cares about changes; cursor-only updates are ignored.
+// Returns false once the UI has gone away.
+async fn refocused(
+    focus_rx: &mut watch::Receiver<Focus>,
+    resource: Resource,
+    seen: &Focus,
+) -> bool {
+    while focus_rx.changed().await.is_ok() {
+        let focus = focus_rx.borrow();
+        if resource.scope(&focus) != resource.scope(seen)
+            || focus.resync != seen.resync
+            || (resource == Resource::Daemon && focus.daemon_check != seen.daemon_check)
+            || focus.paused != seen.paused
+            || focus.live != seen.live
+        {
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,222 @@
+use crate::app::{AppState, ReplayStatus};
+use crate::live::LiveEvent;
+use crate::schema::{Activity, Agent, DaemonStatus, Spawn, TailEntry};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const SPEED_MIN: f64 = 0.25;
+const SPEED_MAX: f64 = 64.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Record {
+    Agents {
+        agents: Vec<Agent>,
+    },
+    Spawns {
+        spawns: Vec<Spawn>,
+    },
+    Activity {
+        agent_id: Option<String>,
+        #[serde(default)]
+        delta: bool,
+        items: Vec<Activity>,
+    },
+    ActivityPage {
+        agent_id: Option<String>,
+        items: Vec<Activity>,
+    },
+    Ledger {
+        #[serde(default)]
+        delta: bool,
+        items: Vec<Activity>,
+    },
+    SpawnEvents {
+        spawn_id: String,
+        items: Vec<Activity>,
+    },
+    Tail {
+        agent: Option<String>,
+        entries: Vec<TailEntry>,
+    },
+    Daemon {
+        status: DaemonStatus,
+    },
+    Live {
+        event: Box<LiveEvent>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub record: Record,
+}
+
+// Entries go to a writer thread so recording never blocks the async runtime on disk.
+pub struct Recorder {
+    tx: Option<mpsc::Sender<Entry>>,
+    writer: Option<thread::JoinHandle<()>>,
+}
+
+impl Recorder {
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        let out = BufWriter::new(File::create(path)?);
+        let (tx, rx) = mpsc::channel();
+        let writer = thread::Builder::new()
+            .name("recorder".to_string())
+            .spawn(move || write_entries(rx, out))?;
+        Ok(Self {
+            tx: Some(tx),
+            writer: Some(writer),
+        })
+    }
+
+    pub fn record(&self, record: Record) {
+        let entry = Entry {
+            at: Utc::now(),
+            record,
+        };
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(entry);
+        }
+    }
+}
+
+impl Drop for Recorder {
+    // Waits for queued entries to reach the file.
+    fn drop(&mut self) {
+        self.tx.take();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+fn write_entries(rx: mpsc::Receiver<Entry>, mut out: BufWriter<File>) {
+    while let Ok(entry) = rx.recv() {
+        for entry in std::iter::once(entry).chain(rx.try_iter()) {
+            if let Ok(line) = serde_json::to_string(&entry) {
+                let _ = writeln!(out, "{}", line);
+            }
+        }
+        // Flush whenever the queue drains so a crash keeps everything captured up to that point.
+        let _ = out.flush();
+    }
+}
+
+pub fn load(path: &Path) -> std::io::Result<Vec<Entry>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+    for (n, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: Entry = serde_json::from_str(&line).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("line {}: {}", n + 1, e),
+            )
+        })?;
+        entries.push(entry);
+    }
+    entries.sort_by_key(|e| e.at);
+    Ok(entries)
+}
+
+pub struct Replay {
+    entries: Vec<Entry>,
+    cursor: usize,
+    playhead: DateTime<Utc>,
+    speed: f64,
+    playing: bool,
+}
+
+impl Replay {
+    pub fn new(entries: Vec<Entry>) -> Option<Self> {
+        let playhead = entries.first()?.at;
+        Some(Self {
+            entries,
+            cursor: 0,
+            playhead,
+            speed: 1.0,
+            playing: true,
+        })
+    }
+
+    fn start(&self) -> DateTime<Utc> {
+        self.entries[0].at
+    }
+
+    fn end(&self) -> DateTime<Utc> {
+        self.entries[self.entries.len() - 1].at
+    }
+
+    pub fn status(&self) -> ReplayStatus {
+        ReplayStatus {
+            playhead: self.playhead,
+            start: self.start(),
+            end: self.end(),
+            speed: self.speed,
+            playing: self.playing,
+        }
+    }
+
+    pub fn toggle_play(&mut self) {
+        if !self.playing && self.playhead >= self.end() {
+            return;
+        }
+        self.playing = !self.playing;
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed * 2.0).min(SPEED_MAX);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = (self.speed / 2.0).max(SPEED_MIN);
+    }
+
+    pub fn advance(&mut self, elapsed: Duration, app_state: &mut AppState) {
+        if self.playing {
+            let step =
+                ChronoDuration::milliseconds((elapsed.as_millis() as f64 * self.speed) as i64);
+            self.playhead = (self.playhead + step).min(self.end());
+            if self.playhead >= self.end() {
+                self.playing = false;
+            }
+        }
+        self.apply_until_playhead(app_state);
+    }
+
+    pub fn seek(&mut self, seconds: i64, app_state: &mut AppState) {
+        let target =
+            (self.playhead + ChronoDuration::seconds(seconds)).clamp(self.start(), self.end());
+        if target < self.playhead {
+            // Records are cumulative, so going back means rebuilding from the start.
+            app_state.clear_data();
+            self.cursor = 0;
+        }
+        self.playhead = target;
+        self.apply_until_playhead(app_state);
+    }
+
+    fn apply_until_playhead(&mut self, app_state: &mut AppState) {
+        while let Some(entry) = self.entries.get(self.cursor) {
+            if entry.at > self.playhead {
+                break;
+            }
+            app_state.apply_record(&entry.record);
+            self.cursor += 1;
+        }
+        app_state.replay = Some(self.status());
+    }
+}
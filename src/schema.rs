@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct DaemonStatus {
     pub running: bool,
     pub pid: Option<i32>,
@@ -10,7 +10,7 @@ pub struct DaemonStatus {
     pub last_skip: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct Agent {
     pub id: String,
@@ -24,7 +24,17 @@ pub struct Agent {
     pub archived_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Agent {
+    pub fn type_icon(&self) -> &'static str {
+        match self.agent_type.as_str() {
+            "ai" => "~",
+            "human" => "*",
+            _ => "?",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct Spawn {
     pub id: String,
@@ -42,10 +52,30 @@ pub struct Spawn {
     pub last_active_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Spawn {
+    pub fn is_failed(&self) -> bool {
+        self.status == "failed" || self.error.is_some()
+    }
+
+    pub fn status_icon(&self) -> &'static str {
+        if self.is_failed() {
+            return "x";
+        }
+        match self.status.as_str() {
+            "active" => "●",
+            "done" => ".",
+            _ => "?",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct Activity {
-    pub id: i64,
+    // A spawn event's position in its spawn's events response; rows without a
+    // numeric id (ledger items go by `primitive_id`) have none.
+    #[serde(default)]
+    pub id: Option<i64>,
     pub agent_id: String,
     pub spawn_id: Option<String>,
     pub primitive: String,
@@ -56,7 +86,7 @@ pub struct Activity {
     pub created_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TailEntry {
     pub spawn: String,
     pub agent: String,
@@ -67,3 +97,83 @@ pub struct TailEntry {
     pub args: Option<String>,
     pub ctx_pct: Option<u32>,
 }
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LedgerQuery {
+    pub agent_id: Option<String>,
+    pub primitive: Option<String>,
+    pub since: Option<String>,
+    pub before: Option<String>,
+    pub limit: usize,
+}
+
+impl LedgerQuery {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            ..Self::default()
+        }
+    }
+
+    pub fn agent(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+
+    pub fn primitive(mut self, primitive: impl Into<String>) -> Self {
+        self.primitive = Some(primitive.into());
+        self
+    }
+
+    pub fn since(mut self, ts: impl Into<String>) -> Self {
+        self.since = Some(ts.into());
+        self
+    }
+
+    pub fn before(mut self, ts: impl Into<String>) -> Self {
+        self.before = Some(ts.into());
+        self
+    }
+
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("limit", self.limit.to_string())];
+        if let Some(agent_id) = &self.agent_id {
+            params.push(("agent_id", agent_id.clone()));
+        }
+        if let Some(primitive) = &self.primitive {
+            params.push(("type", primitive.clone()));
+        }
+        if let Some(since) = &self.since {
+            params.push(("since", since.clone()));
+        }
+        if let Some(before) = &self.before {
+            params.push(("before", before.clone()));
+        }
+        params
+    }
+
+    // Cursors are inclusive so rows sharing the boundary timestamp aren't lost;
+    // callers drop the repeats.
+    pub fn matches(&self, act: &Activity) -> bool {
+        self.agent_id.as_ref().is_none_or(|id| &act.agent_id == id)
+            && self.primitive.as_ref().is_none_or(|p| &act.primitive == p)
+            && self
+                .since
+                .as_ref()
+                .is_none_or(|ts| compare_ts(&act.created_at, ts).is_ge())
+            && self
+                .before
+                .as_ref()
+                .is_none_or(|ts| compare_ts(&act.created_at, ts).is_le())
+    }
+}
+
+fn compare_ts(a: &str, b: &str) -> std::cmp::Ordering {
+    match (
+        chrono::DateTime::parse_from_rfc3339(a),
+        chrono::DateTime::parse_from_rfc3339(b),
+    ) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
@@ -0,0 +1,119 @@
+use regex::{Regex, RegexBuilder};
+
+// Global search queries: free text plus `key:value` field filters, e.g.
+// `agent:sentinel type:tool name:Bash src/.*\.rs`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Pane {
+    Activity,
+    Stream,
+    Ledger,
+    Spawn,
+}
+
+impl Pane {
+    pub const ALL: [Pane; 4] = [Pane::Activity, Pane::Stream, Pane::Ledger, Pane::Spawn];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Pane::Activity => "activity",
+            Pane::Stream => "stream",
+            Pane::Ledger => "ledger",
+            Pane::Spawn => "spawn",
+        }
+    }
+}
+
+// One searchable row, flattened from whichever pane it lives in.
+#[derive(Debug, Clone, Default)]
+pub struct Row<'a> {
+    pub agent: &'a str,
+    pub kind: &'a str,
+    pub name: Option<&'a str>,
+    pub spawn: Option<&'a str>,
+    pub text: Vec<&'a str>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    // Regex over the row's text; smart case, and a literal when it doesn't compile.
+    pub text: Option<Regex>,
+    pub agent: Option<String>,
+    pub kind: Option<String>,
+    pub name: Option<String>,
+    pub spawn: Option<String>,
+    pub pane: Option<Pane>,
+}
+
+fn contains(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(needle)
+}
+
+impl Query {
+    pub fn parse(raw: &str) -> Self {
+        let mut query = Query::default();
+        let mut words = Vec::new();
+        for word in raw.split_whitespace() {
+            let Some((key, value)) = word.split_once(':').filter(|(_, v)| !v.is_empty()) else {
+                words.push(word);
+                continue;
+            };
+            let value = value.to_lowercase();
+            match key {
+                "agent" => query.agent = Some(value.trim_start_matches('@').to_string()),
+                "type" => query.kind = Some(value),
+                "name" => query.name = Some(value),
+                "spawn" => query.spawn = Some(value.trim_start_matches('#').to_string()),
+                "in" => match Pane::ALL.into_iter().find(|p| p.name() == value) {
+                    Some(pane) => query.pane = Some(pane),
+                    None => words.push(word),
+                },
+                _ => words.push(word),
+            }
+        }
+
+        let text = words.join(" ");
+        if !text.is_empty() {
+            let insensitive = !text.chars().any(char::is_uppercase);
+            let build = |pattern: &str| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(insensitive)
+                    .build()
+                    .ok()
+            };
+            query.text = build(&text).or_else(|| build(&regex::escape(&text)));
+        }
+        query
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none()
+            && self.agent.is_none()
+            && self.kind.is_none()
+            && self.name.is_none()
+            && self.spawn.is_none()
+            && self.pane.is_none()
+    }
+
+    // Field filters are case-insensitive substrings, except `spawn:` which is an id prefix.
+    pub fn matches(&self, pane: Pane, row: &Row) -> bool {
+        if self.is_empty() || self.pane.is_some_and(|p| p != pane) {
+            return false;
+        }
+        let field = |filter: &Option<String>, value: Option<&str>| match filter {
+            Some(filter) => value.is_some_and(|v| contains(v, filter)),
+            None => true,
+        };
+        field(&self.agent, Some(row.agent))
+            && field(&self.kind, Some(row.kind))
+            && field(&self.name, row.name)
+            && self
+                .spawn
+                .as_ref()
+                .is_none_or(|prefix| row.spawn.is_some_and(|s| s.starts_with(prefix.as_str())))
+            && self
+                .text
+                .as_ref()
+                .is_none_or(|re| row.text.iter().any(|t| re.is_match(t)))
+    }
+}
@@ -0,0 +1,102 @@
+use super::{DataSource, Result};
+use crate::schema::{Activity, Agent, DaemonStatus, LedgerQuery, Spawn, TailEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Fixture {
+    pub health: serde_json::Value,
+    pub agents: Vec<Agent>,
+    pub spawns: Vec<Spawn>,
+    pub ledger: Vec<Activity>,
+    pub spawn_events: HashMap<String, Vec<Activity>>,
+    pub tail: Vec<TailEntry>,
+    pub daemon: DaemonStatus,
+}
+
+pub struct FixtureSource {
+    fixture: Fixture,
+    label: String,
+}
+
+impl FixtureSource {
+    pub fn new(fixture: Fixture) -> Self {
+        Self {
+            fixture,
+            label: "fixture".to_string(),
+        }
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let raw = std::fs::read_to_string(path)?;
+        let fixture = serde_json::from_str(&raw).map_err(std::io::Error::other)?;
+        Ok(Self {
+            fixture,
+            label: format!("fixture:{}", path.display()),
+        })
+    }
+}
+
+fn last<T: Clone>(items: &[T], limit: usize) -> Vec<T> {
+    items[items.len().saturating_sub(limit)..].to_vec()
+}
+
+impl DataSource for FixtureSource {
+    fn label(&self) -> String {
+        self.label.clone()
+    }
+
+    async fn get_health(&self) -> Result<serde_json::Value> {
+        Ok(self.fixture.health.clone())
+    }
+
+    async fn get_agents(&self) -> Result<Vec<Agent>> {
+        Ok(self.fixture.agents.clone())
+    }
+
+    async fn get_spawns(&self) -> Result<Vec<Spawn>> {
+        Ok(self.fixture.spawns.clone())
+    }
+
+    async fn get_ledger(&self, query: &LedgerQuery) -> Result<Vec<Activity>> {
+        Ok(self
+            .fixture
+            .ledger
+            .iter()
+            .filter(|act| query.matches(act))
+            .take(query.limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_spawn_activity(&self, spawn_id: &str, limit: usize) -> Result<Vec<Activity>> {
+        let events = self
+            .fixture
+            .spawn_events
+            .get(spawn_id)
+            .map(|e| e.as_slice())
+            .unwrap_or_default();
+        Ok(last(events, limit))
+    }
+
+    async fn get_daemon_status(&self) -> Result<DaemonStatus> {
+        Ok(self.fixture.daemon.clone())
+    }
+
+    async fn get_tail(&self, limit: usize) -> Result<Vec<TailEntry>> {
+        Ok(last(&self.fixture.tail, limit))
+    }
+
+    async fn get_agent_tail(&self, agent: &str, limit: usize) -> Result<Vec<TailEntry>> {
+        let entries: Vec<TailEntry> = self
+            .fixture
+            .tail
+            .iter()
+            .filter(|e| e.agent == agent)
+            .cloned()
+            .collect();
+        Ok(last(&entries, limit))
+    }
+}
@@ -0,0 +1,45 @@
+use super::{DataSource, Endpoint, Result};
+use crate::api::ApiClient;
+use crate::schema::{Activity, Agent, DaemonStatus, LedgerQuery, Spawn, TailEntry};
+
+impl DataSource for ApiClient {
+    fn label(&self) -> String {
+        self.base_url().to_string()
+    }
+
+    async fn get_health(&self) -> Result<serde_json::Value> {
+        ApiClient::get_health(self).await
+    }
+
+    async fn get_agents(&self) -> Result<Vec<Agent>> {
+        ApiClient::get_agents(self).await
+    }
+
+    async fn get_spawns(&self) -> Result<Vec<Spawn>> {
+        ApiClient::get_spawns(self).await
+    }
+
+    async fn get_ledger(&self, query: &LedgerQuery) -> Result<Vec<Activity>> {
+        ApiClient::get_ledger(self, query).await
+    }
+
+    async fn get_spawn_activity(&self, spawn_id: &str, limit: usize) -> Result<Vec<Activity>> {
+        ApiClient::get_spawn_activity(self, spawn_id, limit).await
+    }
+
+    async fn get_daemon_status(&self) -> Result<DaemonStatus> {
+        ApiClient::get_daemon_status(self).await
+    }
+
+    async fn get_tail(&self, limit: usize) -> Result<Vec<TailEntry>> {
+        ApiClient::get_tail(self, limit).await
+    }
+
+    async fn get_agent_tail(&self, agent: &str, limit: usize) -> Result<Vec<TailEntry>> {
+        ApiClient::get_agent_tail(self, agent, limit).await
+    }
+
+    fn take_rejected(&self, endpoint: Endpoint) -> Vec<String> {
+        ApiClient::take_rejected(self, endpoint)
+    }
+}
@@ -0,0 +1,312 @@
+use crate::api::{ApiClient, ApiError};
+use crate::cache::{self, Snapshot};
+use crate::config::Instance;
+use crate::record::{Record, Recorder};
+use crate::schema::{Activity, Agent, DaemonStatus, LedgerQuery, Spawn, TailEntry};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+mod fixture;
+mod http;
+
+pub use fixture::{Fixture, FixtureSource};
+
+type Result<T> = std::result::Result<T, ApiError>;
+
+pub trait DataSource: Send + Sync {
+    fn label(&self) -> String;
+
+    fn get_health(&self) -> impl Future<Output = Result<serde_json::Value>> + Send;
+
+    fn get_agents(&self) -> impl Future<Output = Result<Vec<Agent>>> + Send;
+
+    fn get_spawns(&self) -> impl Future<Output = Result<Vec<Spawn>>> + Send;
+
+    fn get_ledger(&self, query: &LedgerQuery)
+    -> impl Future<Output = Result<Vec<Activity>>> + Send;
+
+    fn get_spawn_activity(
+        &self,
+        spawn_id: &str,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<Activity>>> + Send;
+
+    fn get_daemon_status(&self) -> impl Future<Output = Result<DaemonStatus>> + Send;
+
+    fn get_tail(&self, limit: usize) -> impl Future<Output = Result<Vec<TailEntry>>> + Send;
+
+    fn get_agent_tail(
+        &self,
+        agent: &str,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<TailEntry>>> + Send;
+
+    // Rows the backend dropped from its latest `endpoint` response because they didn't decode.
+    fn take_rejected(&self, _endpoint: Endpoint) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn get_agent_identities(&self) -> impl Future<Output = Result<HashMap<String, String>>> + Send {
+        async move {
+            let agents = self.get_agents().await?;
+            Ok(agents.into_iter().map(|a| (a.id, a.identity)).collect())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Endpoint {
+    Agents,
+    Spawns,
+    Ledger,
+    SpawnEvents,
+    Tail,
+    Daemon,
+}
+
+impl Endpoint {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Endpoint::Agents => "agents",
+            Endpoint::Spawns => "spawns",
+            Endpoint::Ledger => "ledger",
+            Endpoint::SpawnEvents => "events",
+            Endpoint::Tail => "tail",
+            Endpoint::Daemon => "daemon",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EndpointStats {
+    pub endpoint: Endpoint,
+    pub last_success: Option<DateTime<Utc>>,
+    pub last_error: Option<ApiError>,
+    pub latency_ms: Option<u128>,
+    pub consecutive_failures: u32,
+    // Rows of the last good response that were skipped as malformed.
+    pub rejected: Vec<String>,
+}
+
+impl EndpointStats {
+    fn new(endpoint: Endpoint) -> Self {
+        Self {
+            endpoint,
+            last_success: None,
+            last_error: None,
+            latency_ms: None,
+            consecutive_failures: 0,
+            rejected: Vec::new(),
+        }
+    }
+
+    pub fn is_failing(&self) -> bool {
+        self.consecutive_failures > 0
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        self.is_failing() && self.last_error.as_ref().is_some_and(ApiError::is_auth)
+    }
+}
+
+pub struct Source<D = ApiClient> {
+    backend: D,
+    stats: Mutex<HashMap<Endpoint, EndpointStats>>,
+    cache_path: Option<PathBuf>,
+    recorder: Option<Recorder>,
+}
+
+impl Source<ApiClient> {
+    pub fn connect(instance: &Instance) -> std::io::Result<Self> {
+        Ok(Self {
+            cache_path: cache::instance_path(instance),
+            ..Self::new(ApiClient::connect(instance)?)
+        })
+    }
+}
+
+impl<D: DataSource> Source<D> {
+    pub fn new(backend: D) -> Self {
+        Self {
+            backend,
+            stats: Mutex::new(HashMap::new()),
+            cache_path: None,
+            recorder: None,
+        }
+    }
+
+    pub fn backend(&self) -> &D {
+        &self.backend
+    }
+
+    pub fn load_cache(&self) -> Option<Snapshot> {
+        self.cache_path.as_deref().and_then(cache::load)
+    }
+
+    pub fn save_cache(&self, snapshot: &Snapshot) -> std::io::Result<()> {
+        match &self.cache_path {
+            Some(path) => cache::save(path, snapshot),
+            None => Ok(()),
+        }
+    }
+
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    pub fn record(&self, make: impl FnOnce() -> Record) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(make());
+        }
+    }
+
+    fn record_ok<T: Clone>(&self, result: &Result<T>, make: impl FnOnce(T) -> Record) {
+        if let Ok(value) = result {
+            self.record(|| make(value.clone()));
+        }
+    }
+
+    pub fn has_fetched(&self) -> bool {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        stats.values().any(|s| s.last_success.is_some())
+    }
+
+    async fn track<T>(
+        &self,
+        endpoint: Endpoint,
+        call: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let started = Instant::now();
+        let result = call.await;
+        let latency_ms = started.elapsed().as_millis();
+
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = stats
+            .entry(endpoint)
+            .or_insert_with(|| EndpointStats::new(endpoint));
+        entry.latency_ms = Some(latency_ms);
+        match &result {
+            Ok(_) => {
+                entry.last_success = Some(Utc::now());
+                entry.consecutive_failures = 0;
+                entry.rejected = self.backend.take_rejected(endpoint);
+            }
+            Err(e) => {
+                entry.last_error = Some(e.clone());
+                entry.consecutive_failures += 1;
+            }
+        }
+        result
+    }
+
+    pub fn endpoint_stats(&self) -> Vec<EndpointStats> {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let mut all: Vec<EndpointStats> = stats.values().cloned().collect();
+        all.sort_by_key(|s| s.endpoint);
+        all
+    }
+
+    pub async fn get_agents(&self) -> Result<Vec<Agent>> {
+        let agents = self
+            .track(Endpoint::Agents, self.backend.get_agents())
+            .await;
+        self.record_ok(&agents, |agents| Record::Agents { agents });
+        agents
+    }
+
+    pub async fn get_agent_identities(&self) -> Result<HashMap<String, String>> {
+        self.track(Endpoint::Agents, self.backend.get_agent_identities())
+            .await
+    }
+
+    pub async fn get_spawns(&self) -> Result<Vec<Spawn>> {
+        let spawns = self
+            .track(Endpoint::Spawns, self.backend.get_spawns())
+            .await;
+        self.record_ok(&spawns, |spawns| Record::Spawns { spawns });
+        spawns
+    }
+
+    pub async fn get_activity(&self, query: &LedgerQuery) -> Result<Vec<Activity>> {
+        let items = self
+            .track(Endpoint::Ledger, self.backend.get_ledger(query))
+            .await;
+        self.record_ok(&items, |items| Record::Activity {
+            agent_id: query.agent_id.clone(),
+            delta: query.since.is_some(),
+            items,
+        });
+        items
+    }
+
+    pub async fn get_activity_page(&self, query: &LedgerQuery) -> Result<Vec<Activity>> {
+        let items = self
+            .track(Endpoint::Ledger, self.backend.get_ledger(query))
+            .await;
+        self.record_ok(&items, |items| Record::ActivityPage {
+            agent_id: query.agent_id.clone(),
+            items,
+        });
+        items
+    }
+
+    pub async fn get_ledger_activity(&self, query: &LedgerQuery) -> Result<Vec<Activity>> {
+        let items = self
+            .track(Endpoint::Ledger, self.backend.get_ledger(query))
+            .await;
+        self.record_ok(&items, |items| Record::Ledger {
+            delta: query.since.is_some(),
+            items,
+        });
+        items
+    }
+
+    pub async fn get_spawn_activity(&self, spawn_id: &str, limit: usize) -> Result<Vec<Activity>> {
+        let items = self
+            .track(
+                Endpoint::SpawnEvents,
+                self.backend.get_spawn_activity(spawn_id, limit),
+            )
+            .await;
+        self.record_ok(&items, |items| Record::SpawnEvents {
+            spawn_id: spawn_id.to_string(),
+            items,
+        });
+        items
+    }
+
+    pub async fn get_daemon_status(&self) -> Result<DaemonStatus> {
+        let status = self
+            .track(Endpoint::Daemon, self.backend.get_daemon_status())
+            .await;
+        self.record_ok(&status, |status| Record::Daemon { status });
+        status
+    }
+
+    pub async fn get_tail(&self, limit: usize) -> Result<Vec<TailEntry>> {
+        let entries = self
+            .track(Endpoint::Tail, self.backend.get_tail(limit))
+            .await;
+        self.record_ok(&entries, |entries| Record::Tail {
+            agent: None,
+            entries,
+        });
+        entries
+    }
+
+    pub async fn get_agent_tail(&self, agent: &str, limit: usize) -> Result<Vec<TailEntry>> {
+        let entries = self
+            .track(Endpoint::Tail, self.backend.get_agent_tail(agent, limit))
+            .await;
+        self.record_ok(&entries, |entries| Record::Tail {
+            agent: Some(agent.to_string()),
+            entries,
+        });
+        entries
+    }
+}
@@ -8,8 +8,19 @@ pub fn format_elapsed_time(iso_timestamp: &str) -> String {
         .unwrap_or_default()
         .as_secs();
 
-    let elapsed = now.saturating_sub(created);
+    format_duration(now.saturating_sub(created))
+}
+
+pub fn format_span(start_iso: &str, end_iso: Option<&str>) -> String {
+    match end_iso {
+        Some(end) => {
+            format_duration(parse_iso_timestamp(end).saturating_sub(parse_iso_timestamp(start_iso)))
+        }
+        None => format_elapsed_time(start_iso),
+    }
+}
 
+pub fn format_duration(elapsed: u64) -> String {
     if elapsed < 60 {
         format!("{}s", elapsed)
     } else if elapsed < 3600 {
@@ -95,6 +106,14 @@ mod tests {
         assert!(formatted.contains('h'));
     }
 
+    #[test]
+    fn span_between_timestamps() {
+        assert_eq!(
+            format_span("2025-01-01T10:00:00Z", Some("2025-01-01T10:01:30Z")),
+            "1m30s"
+        );
+    }
+
     #[test]
     fn parse_rfc3339_with_offset() {
         let zulu = parse_iso_timestamp("2025-01-01T10:00:00Z");
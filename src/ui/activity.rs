@@ -7,6 +7,8 @@ use ratatui::{
 };
 
 use crate::app::AppState;
+use crate::search::Pane;
+use crate::source::Endpoint;
 
 const TIME_SLICE_START: usize = 11;
 const TIME_SLICE_END: usize = 19;
@@ -70,20 +72,28 @@ fn format_activity_line<'a>(
 pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
     let count = app_state.activity.len();
     let pause_tag = if app_state.paused { " ⏸" } else { "" };
+    let stale = super::stale_tag(app_state, Endpoint::Ledger);
     let title = if app_state.all_stream {
-        format!("Activity (all, {}){}", count, pause_tag)
+        format!("Activity (all, {}){}{}", count, pause_tag, stale)
     } else if let Some(agent) = app_state.active_agent() {
-        format!("Activity ({}, {}){}", agent.identity, count, pause_tag)
+        format!(
+            "Activity ({}, {}){}{}",
+            agent.identity, count, pause_tag, stale
+        )
     } else {
-        format!("Activity ({}){}", count, pause_tag)
+        format!("Activity ({}){}{}", count, pause_tag, stale)
     };
 
     let items: Vec<ListItem> = app_state
         .activity
         .iter()
+        .enumerate()
         .skip(app_state.activity_scroll_offset)
         .take(area.height.saturating_sub(2) as usize)
-        .map(|act| ListItem::new(Line::from(format_activity_line(app_state, act))))
+        .map(|(idx, act)| {
+            let line = Line::from(format_activity_line(app_state, act));
+            ListItem::new(super::search_marks(app_state, Pane::Activity, idx, line))
+        })
         .collect();
 
     let list = List::new(items)
@@ -0,0 +1,91 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::AppState;
+use crate::keymap::Action;
+
+const KEYS_WIDTH: usize = 14;
+
+fn binding_line(app_state: &AppState, action: Action) -> Line<'static> {
+    let keys = app_state
+        .keymap
+        .keys(action)
+        .iter()
+        .map(|k| k.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let keys = if keys.is_empty() {
+        "—".to_string()
+    } else {
+        keys
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("{:>width$}  ", keys, width = KEYS_WIDTH),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(action.summary()),
+    ])
+}
+
+// Lists the normal-mode bindings from the active keymap, in two columns.
+pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
+    let rows = Action::ALL.len().div_ceil(2);
+    let height = (rows as u16 + 5).min(area.height);
+    let width = 100.min(area.width);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title(" Keys — normal mode ")
+        .title_bottom(Line::from(Span::styled(
+            " any key closes ",
+            Style::default().fg(Color::DarkGray),
+        )))
+        .borders(Borders::ALL);
+    let inner = block.inner(popup);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(block, popup);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(2)])
+        .split(inner);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(sections[0]);
+
+    let (left, right) = Action::ALL.split_at(rows);
+    for (actions, column) in [(left, columns[0]), (right, columns[1])] {
+        let lines: Vec<Line> = actions
+            .iter()
+            .map(|a| binding_line(app_state, *a))
+            .collect();
+        frame.render_widget(Paragraph::new(lines), column);
+    }
+
+    let hint = Style::default().fg(Color::DarkGray);
+    let footer = vec![
+        Line::from(Span::styled(
+            "  insert mode: esc back to normal · enter send/run · ↑/↓ history matching the typed prefix · ctrl+r search",
+            hint,
+        )),
+        Line::from(Span::styled(
+            "  alt+<letter> focus agent by initial · rebind under [keys] in config.toml",
+            hint,
+        )),
+    ];
+    frame.render_widget(Paragraph::new(footer), sections[1]);
+}
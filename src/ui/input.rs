@@ -2,71 +2,248 @@ use ratatui::{
     Frame,
     layout::Rect,
     style::{Color, Modifier, Style},
-    text::Span,
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
 };
 
 use crate::app::{AppState, AutocompleteMode};
+use crate::command;
+use crate::keymap::Action;
+
+// The input bar grows with multiline input, up to this many rows.
+const MAX_LINES: usize = 6;
+const DROPDOWN_ROWS: usize = 10;
+
+pub fn height(app_state: &AppState) -> u16 {
+    app_state.input_line_count().min(MAX_LINES) as u16
+}
 
 pub fn render_input_bar(frame: &mut Frame, app_state: &AppState, area: Rect) {
-    let prompt = if app_state.paused {
-        "[PAUSED] "
+    if let Some(confirm) = &app_state.confirm {
+        let prompt = Paragraph::new(format!("! {}", confirm.prompt))
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        frame.render_widget(prompt, area);
+        return;
+    }
+    if app_state.global_search.is_some() && !app_state.is_inserting() {
+        render_search_bar(frame, app_state, area);
+        return;
+    }
+
+    let prompt = if let Some(search) = &app_state.history_search {
+        let failing = if app_state.search_match().is_none() {
+            "failing "
+        } else {
+            ""
+        };
+        let scope = search
+            .scope
+            .as_deref()
+            .map(|s| format!(" {}", s))
+            .unwrap_or_default();
+        format!("({}reverse-i-search{})`{}': ", failing, scope, search.query)
+    } else if app_state.paused {
+        "[PAUSED] ".to_string()
     } else if app_state.all_stream {
-        "[ALL] "
+        "[ALL] ".to_string()
     } else {
-        "> "
+        "> ".to_string()
     };
-    let text = format!("{}{}", prompt, app_state.input_text);
+    let mode = if app_state.is_inserting() {
+        Span::styled(
+            "INSERT ",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::styled("NORMAL ", Style::default().fg(Color::DarkGray))
+    };
+    let indent = mode.width() + Span::raw(prompt.as_str()).width();
+    let mut text_lines = app_state.input_text.split('\n');
+    let mut spans = vec![
+        mode,
+        Span::raw(prompt),
+        Span::raw(text_lines.next().unwrap_or_default()),
+    ];
+    if !app_state.is_inserting() && app_state.input_text.is_empty() {
+        let key = |action| {
+            app_state
+                .keymap
+                .keys(action)
+                .first()
+                .map(|k| k.to_string())
+                .unwrap_or_default()
+        };
+        spans.push(Span::styled(
+            format!(
+                "{} to type, {} command, {} keys",
+                key(Action::Insert),
+                key(Action::Command),
+                key(Action::Help)
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if let Some(spec) =
+        command::split(&app_state.input_text).and_then(|(name, _)| command::spec(name))
+    {
+        spans.push(Span::styled(
+            format!("  {} — {}", spec.usage, spec.summary),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let mut lines = vec![Line::from(spans)];
+    lines.extend(text_lines.map(|l| Line::from(format!("{}{}", " ".repeat(indent), l))));
+
+    // Keep the cursor in view: scroll down to its line and right past long lines.
+    let before = &app_state.input_text[..app_state.cursor()];
+    let row = before.matches('\n').count();
+    let column = indent + Span::raw(before.rsplit('\n').next().unwrap_or_default()).width();
+    let top = row.saturating_sub(area.height.max(1) as usize - 1);
+    let left = column.saturating_sub(area.width.max(1) as usize - 1);
 
-    let input = Paragraph::new(text)
-        .block(Block::default().borders(Borders::TOP))
+    let input = Paragraph::new(lines)
+        .scroll((top as u16, left as u16))
         .style(Style::default().fg(Color::Cyan));
 
     frame.render_widget(input, area);
 
-    if let Some(mode) = app_state.autocomplete_mode
-        && area.height > 1
+    if app_state.is_inserting() && !app_state.help && app_state.transcript.is_none() {
+        frame.set_cursor_position((area.x + (column - left) as u16, area.y + (row - top) as u16));
+    }
+
+    if app_state.is_completing() {
+        render_dropdown(frame, app_state, area);
+    }
+}
+
+// `/query` while typing it, then the position among the hits for n/N.
+fn render_search_bar(frame: &mut Frame, app_state: &AppState, area: Rect) {
+    let Some(search) = &app_state.global_search else {
+        return;
+    };
+    let hits = app_state.global_search_hits();
+    let status = match search
+        .current
+        .and_then(|c| hits.iter().position(|h| *h == c))
     {
-        let dropdown_area = Rect {
-            x: area.x,
-            y: area.y + 1,
-            width: area.width,
-            height: (area.height - 1).min(10),
-        };
+        _ if hits.is_empty() && !search.query.is_empty() => "  no matches".to_string(),
+        _ if hits.is_empty() => String::new(),
+        Some(i) => format!("  {}/{} (n/N)", i + 1, hits.len()),
+        None => format!("  {} matches", hits.len()),
+    };
+    let prompt = format!("/{}", search.input);
+    let spans = vec![
+        Span::styled(
+            "SEARCH ",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(prompt.clone(), Style::default().fg(Color::Cyan)),
+        Span::styled(status, Style::default().fg(Color::DarkGray)),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
 
-        let items: Vec<ListItem> = app_state
-            .autocomplete_list
-            .iter()
-            .enumerate()
-            .take(10)
-            .map(|(idx, item)| {
-                let prefix = if idx == app_state.autocomplete_idx {
-                    "> "
-                } else {
-                    "  "
-                };
+    if search.editing && !app_state.help && app_state.transcript.is_none() {
+        let column = Span::raw("SEARCH ").width() + Span::raw(prompt.as_str()).width();
+        let column = column.min(area.width.saturating_sub(1) as usize);
+        frame.set_cursor_position((area.x + column as u16, area.y));
+    }
+}
 
-                let style = if idx == app_state.autocomplete_idx {
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default().fg(Color::White)
-                };
+// Splits `text` into spans, highlighting the chars at `positions`.
+fn highlighted(text: &str, positions: &[usize], style: Style) -> Vec<Span<'static>> {
+    let mark = style
+        .fg(Color::Green)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_marked = false;
+    for (i, ch) in text.chars().enumerate() {
+        let marked = positions.contains(&i);
+        if marked != run_marked && !run.is_empty() {
+            let style = if run_marked { mark } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_marked = marked;
+        run.push(ch);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, if run_marked { mark } else { style }));
+    }
+    spans
+}
 
-                let label = match mode {
-                    AutocompleteMode::Agent => format!("@ {}", item),
-                    AutocompleteMode::File => format!("/ {}", item),
-                };
+// Opens upwards, over the panes, so it never hides what's being typed.
+fn render_dropdown(frame: &mut Frame, app_state: &AppState, area: Rect) {
+    let Some(mode) = app_state.autocomplete_mode else {
+        return;
+    };
+    let rows = app_state.autocomplete_list.len().min(DROPDOWN_ROWS);
+    let height = (rows as u16 + 2).min(area.y);
+    let dropdown_area = Rect {
+        x: area.x,
+        y: area.y - height,
+        width: area.width,
+        height,
+    };
+    let first = app_state.autocomplete_idx.saturating_sub(DROPDOWN_ROWS - 1);
+    let trigger = match mode {
+        AutocompleteMode::Agent => "@",
+        AutocompleteMode::Spawn => "#",
+        AutocompleteMode::Command => ":",
+        _ => "",
+    };
+
+    let items: Vec<ListItem> = app_state
+        .autocomplete_list
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(DROPDOWN_ROWS)
+        .map(|(idx, item)| {
+            let selected = idx == app_state.autocomplete_idx;
+            let style = if selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let hint = app_state.autocomplete_hints.get(item);
+            let found = app_state.autocomplete_match(item);
+            let positions = |in_detail: bool| match &found {
+                Some((m, d)) if *d == in_detail => m.positions.clone(),
+                _ => Vec::new(),
+            };
 
-                ListItem::new(Span::styled(format!("{}{}", prefix, label), style))
-            })
-            .collect();
+            let mut spans = vec![Span::styled(if selected { "> " } else { "  " }, style)];
+            if let Some(icon) = hint.map(|h| h.icon).filter(|i| !i.is_empty()) {
+                spans.push(Span::styled(format!("{} ", icon), style));
+            }
+            spans.push(Span::styled(trigger, style));
+            spans.extend(highlighted(item, &positions(false), style));
+            if let Some(detail) = hint.map(|h| h.detail.as_str()).filter(|d| !d.is_empty()) {
+                // Command summaries line up in a column.
+                let pad = match mode {
+                    AutocompleteMode::Command => 12usize.saturating_sub(item.chars().count()),
+                    _ => 0,
+                };
+                spans.push(Span::raw(" ".repeat(pad + 2)));
+                let dim = Style::default().fg(Color::DarkGray);
+                spans.extend(highlighted(detail, &positions(true), dim));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
 
-        let list = List::new(items)
-            .block(Block::default().borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM))
-            .style(Style::default().fg(Color::White));
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(Color::White));
 
-        frame.render_widget(list, dropdown_area);
-    }
+    frame.render_widget(Clear, dropdown_area);
+    frame.render_widget(list, dropdown_area);
 }
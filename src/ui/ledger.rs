@@ -7,6 +7,8 @@ use ratatui::{
 };
 
 use crate::app::AppState;
+use crate::search::Pane;
+use crate::source::Endpoint;
 
 const TIME_SLICE_START: usize = 11;
 const TIME_SLICE_END: usize = 19;
@@ -22,14 +24,19 @@ fn primitive_color(primitive: &str) -> Color {
 
 pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
     let count = app_state.ledger.len();
-    let title = format!("Ledger ({})", count);
+    let title = format!(
+        "Ledger ({}){}",
+        count,
+        super::stale_tag(app_state, Endpoint::Ledger)
+    );
 
     let items: Vec<ListItem> = app_state
         .ledger
         .iter()
+        .enumerate()
         .skip(app_state.ledger_scroll_offset)
         .take(area.height.saturating_sub(2) as usize)
-        .map(|act| {
+        .map(|(idx, act)| {
             let timestamp = act
                 .created_at
                 .get(TIME_SLICE_START..TIME_SLICE_END)
@@ -70,7 +77,7 @@ pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
                 Span::styled(detail, Style::default().fg(Color::DarkGray)),
             ]);
 
-            ListItem::new(line)
+            ListItem::new(super::search_marks(app_state, Pane::Ledger, idx, line))
         })
         .collect();
 
@@ -1,16 +1,82 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
 };
+use regex::Regex;
 
 use crate::app::{AppState, RightPane};
+use crate::search::Pane;
+use crate::source::Endpoint;
 
 mod activity;
+mod help;
 mod input;
 mod ledger;
 mod sidebar;
+mod spawn;
 mod status;
 mod stream;
+mod transcript;
+
+fn stale_tag(app_state: &AppState, endpoint: Endpoint) -> &'static str {
+    if app_state.is_stale(endpoint) {
+        " [stale]"
+    } else {
+        ""
+    }
+}
+
+fn split_matches<'a>(span: Span<'a>, re: &Regex, hit: Style) -> Vec<Span<'a>> {
+    let text = span.content.to_string();
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for m in re.find_iter(&text).filter(|m| !m.is_empty()) {
+        if m.start() > pos {
+            spans.push(Span::styled(text[pos..m.start()].to_string(), span.style));
+        }
+        spans.push(Span::styled(m.as_str().to_string(), span.style.patch(hit)));
+        pos = m.end();
+    }
+    if spans.is_empty() {
+        return vec![span];
+    }
+    if pos < text.len() {
+        spans.push(Span::styled(text[pos..].to_string(), span.style));
+    }
+    spans
+}
+
+// Global search marks for one row: regex matches on yellow (the whole row when
+// the query is only field filters), and a gray bar behind the current hit.
+fn search_marks<'a>(app_state: &AppState, pane: Pane, index: usize, line: Line<'a>) -> Line<'a> {
+    if !app_state.is_search_hit(pane, index) {
+        return line;
+    }
+    let hit = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let spans: Vec<Span> = match app_state
+        .global_search_query()
+        .and_then(|q| q.text.as_ref())
+    {
+        Some(re) => line
+            .spans
+            .into_iter()
+            .flat_map(|span| split_matches(span, re, hit))
+            .collect(),
+        None => line
+            .spans
+            .into_iter()
+            .map(|span| span.patch_style(Style::default().fg(Color::Yellow)))
+            .collect(),
+    };
+    let line = Line::from(spans);
+    if app_state.is_current_hit(pane, index) {
+        line.style(Style::default().bg(Color::DarkGray))
+    } else {
+        line
+    }
+}
 
 pub fn render_ui(frame: &mut Frame, app_state: &AppState) {
     let main_layout = Layout::default()
@@ -18,7 +84,7 @@ pub fn render_ui(frame: &mut Frame, app_state: &AppState) {
         .constraints([
             Constraint::Min(1),
             Constraint::Length(1),
-            Constraint::Length(1),
+            Constraint::Length(input::height(app_state)),
         ])
         .split(frame.area());
 
@@ -29,9 +95,9 @@ pub fn render_ui(frame: &mut Frame, app_state: &AppState) {
     let horizontal = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Percentage(25),
-            Constraint::Percentage(50),
-            Constraint::Percentage(25),
+            Constraint::Percentage(app_state.layout.sidebar),
+            Constraint::Percentage(app_state.layout.activity),
+            Constraint::Percentage(app_state.layout.right),
         ])
         .split(content_area);
 
@@ -44,7 +110,15 @@ pub fn render_ui(frame: &mut Frame, app_state: &AppState) {
     match app_state.right_pane {
         RightPane::Stream => stream::render(frame, app_state, right_area),
         RightPane::Ledger => ledger::render(frame, app_state, right_area),
+        RightPane::SpawnDetail => spawn::render(frame, app_state, right_area),
     }
     status::render(frame, app_state, status_area);
     input::render_input_bar(frame, app_state, input_area);
+
+    if app_state.transcript.is_some() {
+        transcript::render(frame, app_state, content_area);
+    }
+    if app_state.help {
+        help::render(frame, app_state, frame.area());
+    }
 }
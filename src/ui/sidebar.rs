@@ -7,10 +7,16 @@ use ratatui::{
 };
 
 use crate::app::{AppState, SidebarTab};
-use crate::time::format_elapsed_time;
+use crate::source::Endpoint;
+use crate::time::{format_elapsed_time, format_span};
 
 pub fn render_sidebar(frame: &mut Frame, app_state: &AppState, area: Rect) {
-    let tab_titles = vec!["AGENTS", "SPAWNS"];
+    let spawns_title = if app_state.spawn_tree {
+        "TREE"
+    } else {
+        "SPAWNS"
+    };
+    let tab_titles = vec!["AGENTS", spawns_title];
     let tab_index = match app_state.active_tab {
         SidebarTab::Agents => 0,
         SidebarTab::Spawns => 1,
@@ -42,6 +48,13 @@ pub fn render_sidebar(frame: &mut Frame, app_state: &AppState, area: Rect) {
     }
 }
 
+fn instance_tag(app_state: &AppState, id: &str) -> Span<'static> {
+    match app_state.instance_tag(id) {
+        Some(name) => Span::styled(format!(" @{}", name), Style::default().fg(Color::DarkGray)),
+        None => Span::raw(""),
+    }
+}
+
 fn spawn_bar(active: usize, total: usize, width: usize) -> String {
     if total == 0 {
         return " ".repeat(width);
@@ -69,11 +82,7 @@ fn render_agents_list(frame: &mut Frame, app_state: &AppState, area: Rect) {
                 " "
             };
 
-            let type_icon = match agent.agent_type.as_str() {
-                "ai" => "~",
-                "human" => "*",
-                _ => "?",
-            };
+            let type_icon = agent.type_icon();
 
             let active_spawns = app_state
                 .spawns
@@ -119,6 +128,7 @@ fn render_agents_list(frame: &mut Frame, app_state: &AppState, area: Rect) {
                         Color::DarkGray
                     }),
                 ),
+                instance_tag(app_state, &agent.id),
             ]);
 
             ListItem::new(line)
@@ -126,7 +136,11 @@ fn render_agents_list(frame: &mut Frame, app_state: &AppState, area: Rect) {
         .collect();
 
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL))
+        .block(
+            Block::default()
+                .title(super::stale_tag(app_state, Endpoint::Agents).trim_start())
+                .borders(Borders::ALL),
+        )
         .style(Style::default().fg(Color::White));
 
     frame.render_widget(list, area);
@@ -135,7 +149,9 @@ fn render_agents_list(frame: &mut Frame, app_state: &AppState, area: Rect) {
 fn render_spawns_list(frame: &mut Frame, app_state: &AppState, area: Rect) {
     let mut items: Vec<ListItem> = Vec::new();
 
-    for (idx, spawn) in app_state.spawns.iter().enumerate() {
+    for row in app_state.visible_spawn_rows() {
+        let idx = row.idx;
+        let spawn = &app_state.spawns[idx];
         let is_focused = idx == app_state.active_spawn_idx;
         let is_selected = app_state.selected_spawn_idx == Some(idx);
         let is_expanded = app_state.expanded_spawns.contains(&spawn.id);
@@ -147,18 +163,61 @@ fn render_spawns_list(frame: &mut Frame, app_state: &AppState, area: Rect) {
             _ => " ",
         };
 
-        let status_icon = match spawn.status.as_str() {
-            "active" => "●",
-            "done" if spawn.error.is_some() => "x",
-            "done" => ".",
-            _ => "?",
-        };
+        let status_icon = spawn.status_icon();
 
-        let elapsed = format_elapsed_time(&spawn.created_at);
         let identity = app_state.resolve_identity(&spawn.agent_id);
-        let name = format!("{} {} {} ({})", indicator, status_icon, identity, elapsed);
+        let flag = if app_state.flagged_spawns.contains(&spawn.id) {
+            Span::styled(" ⚑", Style::default().fg(Color::Yellow))
+        } else {
+            Span::raw("")
+        };
 
-        items.push(ListItem::new(name));
+        if app_state.spawn_tree {
+            let collapsed = app_state.collapsed_spawns.contains(&spawn.id);
+            let branch = match (row.descendants, collapsed) {
+                (0, _) => " ",
+                (_, true) => "▸",
+                (_, false) => "▾",
+            };
+            let hidden = if collapsed && row.descendants > 0 {
+                format!(" +{}", row.descendants)
+            } else {
+                String::new()
+            };
+            let color = if row.failed {
+                Color::Red
+            } else if row.active {
+                Color::Green
+            } else {
+                Color::White
+            };
+
+            items.push(ListItem::new(Line::from(vec![
+                Span::raw(format!(
+                    "{} {}{} ",
+                    indicator,
+                    "  ".repeat(row.depth),
+                    branch
+                )),
+                Span::styled(status_icon, Style::default().fg(color)),
+                Span::raw(format!(
+                    " {} ({}){}",
+                    identity,
+                    format_span(&row.start, row.end.as_deref()),
+                    hidden
+                )),
+                flag,
+                instance_tag(app_state, &spawn.id),
+            ])));
+        } else {
+            let elapsed = format_elapsed_time(&spawn.created_at);
+            let name = format!("{} {} {} ({})", indicator, status_icon, identity, elapsed);
+            items.push(ListItem::new(Line::from(vec![
+                Span::raw(name),
+                flag,
+                instance_tag(app_state, &spawn.id),
+            ])));
+        }
 
         if is_expanded {
             if let Some(summary) = &spawn.summary {
@@ -181,7 +240,11 @@ fn render_spawns_list(frame: &mut Frame, app_state: &AppState, area: Rect) {
     }
 
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL))
+        .block(
+            Block::default()
+                .title(super::stale_tag(app_state, Endpoint::Spawns).trim_start())
+                .borders(Borders::ALL),
+        )
         .style(Style::default().fg(Color::White));
 
     frame.render_widget(list, area);
@@ -0,0 +1,173 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+use crate::app::AppState;
+use crate::schema::{Activity, Spawn};
+use crate::search::Pane;
+use crate::source::Endpoint;
+use crate::time::format_elapsed_time;
+
+const TIME_SLICE_START: usize = 11;
+const TIME_SLICE_END: usize = 19;
+const HEADER_HEIGHT: u16 = 5;
+
+fn short(id: &str) -> &str {
+    &id[..id.len().min(8)]
+}
+
+fn status_color(spawn: &Spawn) -> Color {
+    if spawn.is_failed() {
+        return Color::Red;
+    }
+    match spawn.status.as_str() {
+        "active" => Color::Green,
+        "done" => Color::Blue,
+        _ => Color::Yellow,
+    }
+}
+
+fn field(label: &str, value: Option<&str>) -> Vec<Span<'static>> {
+    vec![
+        Span::styled(format!("{}:", label), Style::default().fg(Color::DarkGray)),
+        Span::raw(format!("{} ", value.unwrap_or("-"))),
+    ]
+}
+
+fn header_lines<'a>(app_state: &'a AppState, spawn: &'a Spawn) -> Vec<Line<'a>> {
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut status_line = vec![
+        Span::styled(
+            format!("{} ", app_state.resolve_identity(&spawn.agent_id)),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("[{}] ", spawn.status),
+            Style::default().fg(status_color(spawn)),
+        ),
+        Span::styled(format_elapsed_time(&spawn.created_at), dim),
+    ];
+    if let Some(error) = &spawn.error {
+        status_line.push(Span::styled(
+            format!(" err: {}", error),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    let pid = spawn.pid.map(|p| p.to_string());
+    let mut ids = field("pid", pid.as_deref());
+    ids.extend(field("session", spawn.session_id.as_deref().map(short)));
+
+    let mut origin = field("source", spawn.source.as_deref());
+    origin.extend(field("caller", spawn.caller_spawn_id.as_deref().map(short)));
+
+    vec![
+        Line::from(status_line),
+        Line::from(ids),
+        Line::from(field("trace", spawn.trace_hash.as_deref())),
+        Line::from(origin),
+    ]
+}
+
+fn event_color(kind: &str) -> Color {
+    match kind {
+        "text" => Color::White,
+        "tool_call" | "tool" => Color::Yellow,
+        "tool_result" | "result" => Color::Green,
+        "error" => Color::Red,
+        _ => Color::DarkGray,
+    }
+}
+
+fn event_item<'a>(app_state: &AppState, idx: usize, act: &'a Activity) -> ListItem<'a> {
+    let timestamp = act
+        .created_at
+        .get(TIME_SLICE_START..TIME_SLICE_END)
+        .unwrap_or("??:??:??");
+    let color = event_color(&act.primitive);
+
+    let mut head = vec![
+        Span::styled(
+            format!("{} ", timestamp),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            act.primitive.clone(),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if let Some(name) = &act.field {
+        head.push(Span::styled(
+            format!(" {}", name),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    let mut lines = vec![Line::from(head)];
+    if let Some(body) = &act.after {
+        lines.extend(
+            body.lines()
+                .map(|l| Line::from(Span::styled(format!("  {}", l), Style::default().fg(color)))),
+        );
+    }
+    ListItem::new(
+        lines
+            .into_iter()
+            .map(|line| super::search_marks(app_state, Pane::Spawn, idx, line))
+            .collect::<Vec<_>>(),
+    )
+}
+
+pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
+    let Some(spawn) = app_state.selected_spawn() else {
+        let empty = Paragraph::new("Select a spawn with Ctrl+j/k")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().title("Spawn").borders(Borders::ALL));
+        frame.render_widget(empty, area);
+        return;
+    };
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(HEADER_HEIGHT), Constraint::Min(1)])
+        .split(area);
+
+    let header = Paragraph::new(header_lines(app_state, spawn)).block(
+        Block::default()
+            .title(format!("Spawn {}", short(&spawn.id)))
+            .title_alignment(Alignment::Left)
+            .borders(Borders::LEFT | Borders::RIGHT | Borders::TOP),
+    );
+    frame.render_widget(header, layout[0]);
+
+    let count = app_state.spawn_activity.len();
+    let items: Vec<ListItem> = app_state
+        .spawn_activity
+        .iter()
+        .enumerate()
+        .skip(app_state.spawn_activity_scroll_offset)
+        .map(|(idx, act)| event_item(app_state, idx, act))
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(
+                    "Events ({}){}",
+                    count,
+                    super::stale_tag(app_state, Endpoint::SpawnEvents)
+                ))
+                .title_alignment(Alignment::Left)
+                .borders(Borders::ALL),
+        )
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(list, layout[1]);
+}
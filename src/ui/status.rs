@@ -6,25 +6,163 @@ use ratatui::{
     widgets::Paragraph,
 };
 
-use crate::app::AppState;
-use crate::time::format_elapsed_time;
+use crate::app::{AppState, DaemonChange, ReplayStatus, ToastKind};
+use crate::time::{format_duration, format_elapsed_time};
+
+fn replay_spans(replay: &ReplayStatus) -> Vec<Span<'static>> {
+    let (icon, color) = if replay.playing {
+        ("⏵", Color::Magenta)
+    } else {
+        ("⏸", Color::Yellow)
+    };
+    let offset = (replay.playhead - replay.start).num_seconds().max(0) as u64;
+    let total = (replay.end - replay.start).num_seconds().max(0) as u64;
+    vec![Span::styled(
+        format!(
+            " {} REPLAY {} {}/{} ×{}",
+            icon,
+            replay.playhead.format("%H:%M:%S"),
+            format_duration(offset),
+            format_duration(total),
+            replay.speed
+        ),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )]
+}
+
+fn instance_spans(app_state: &AppState) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for (idx, instance) in app_state.instances.iter().enumerate() {
+        let (icon, color) = if instance.endpoints.is_empty() {
+            ("?", Color::DarkGray)
+        } else if instance.is_unauthorized() {
+            ("⊘", Color::Red)
+        } else if instance.is_down() {
+            ("✗", Color::Red)
+        } else if instance.is_degraded() {
+            ("!", Color::Yellow)
+        } else {
+            ("✓", Color::Green)
+        };
+        let mut style = Style::default().fg(color);
+        if idx == app_state.instance {
+            style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        }
+        spans.push(Span::styled(format!(" {} {}", instance.name, icon), style));
+    }
+    if app_state.merged {
+        spans.push(Span::styled(
+            " MERGED",
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans
+}
+
+fn connection_spans(app_state: &AppState) -> Vec<Span<'static>> {
+    let worst = app_state.worst_endpoint();
+    let (label, color) = if app_state.endpoints.is_empty() {
+        ("API ?".to_string(), Color::DarkGray)
+    } else if app_state.is_unauthorized() {
+        ("API ⊘ AUTH".to_string(), Color::Red)
+    } else if app_state.api_down() {
+        ("API ✗".to_string(), Color::Red)
+    } else if worst.is_some() || app_state.skipping_endpoint().is_some() {
+        ("API !".to_string(), Color::Yellow)
+    } else {
+        let latency = app_state
+            .endpoints
+            .iter()
+            .filter_map(|s| s.latency_ms)
+            .max()
+            .unwrap_or(0);
+        (format!("API ✓ {}ms", latency), Color::Green)
+    };
+
+    let mut spans = vec![Span::styled(
+        format!(" {}", label),
+        Style::default().fg(color),
+    )];
+    let fetched = app_state.endpoints.iter().any(|s| s.last_success.is_some());
+    if let Some(saved_at) = app_state.cached_at
+        && !fetched
+    {
+        spans.push(Span::styled(
+            format!(
+                " cached {} ago",
+                format_elapsed_time(&saved_at.to_rfc3339())
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if let Some(stats) = worst {
+        let error = stats
+            .last_error
+            .as_ref()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        let last_ok = stats
+            .last_success
+            .map(|ts| format!(", ok {} ago", format_elapsed_time(&ts.to_rfc3339())))
+            .unwrap_or_default();
+        spans.push(Span::styled(
+            format!(
+                " {}: {} ({}×{})",
+                stats.endpoint.label(),
+                error,
+                stats.consecutive_failures,
+                last_ok
+            ),
+            Style::default().fg(color),
+        ));
+    } else if let Some(stats) = app_state.skipping_endpoint() {
+        spans.push(Span::styled(
+            format!(
+                " {}: skipped {} bad rows ({})",
+                stats.endpoint.label(),
+                stats.rejected.len(),
+                stats.rejected[0]
+            ),
+            Style::default().fg(color),
+        ));
+    }
+    spans
+}
 
 pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
     let d = &app_state.daemon;
 
-    let (daemon_icon, daemon_color) = if d.running {
-        ("●", Color::Green)
-    } else {
-        ("○", Color::Red)
+    let pending = app_state.daemon_pending.as_ref().map(|p| p.change);
+
+    let (daemon_icon, daemon_color) = match pending {
+        Some(DaemonChange::Running(true)) => ("◐", Color::Yellow),
+        Some(DaemonChange::Running(false)) => ("◑", Color::Yellow),
+        _ if d.running => ("●", Color::Green),
+        _ => ("○", Color::Red),
+    };
+
+    let (swarm_label, swarm_color) = match pending {
+        Some(DaemonChange::Enabled(true)) => ("ON…", Color::Yellow),
+        Some(DaemonChange::Enabled(false)) => ("OFF…", Color::Yellow),
+        _ if d.enabled => ("ON", Color::Green),
+        _ => ("OFF", Color::DarkGray),
     };
 
-    let (swarm_label, swarm_color) = if d.enabled {
-        ("ON", Color::Green)
+    let (source_label, source_color) = if app_state.live {
+        ("LIVE", Color::Green)
     } else {
-        ("OFF", Color::DarkGray)
+        ("POLL", Color::Yellow)
     };
 
-    let slots = format!("{}/{}", d.active_count, d.concurrency);
+    let (slots, slots_color) = match pending {
+        Some(DaemonChange::Concurrency(n)) => (
+            format!("{}/{}→{}…", d.active_count, d.concurrency, n),
+            Color::Yellow,
+        ),
+        _ => (format!("{}/{}", d.active_count, d.concurrency), Color::Cyan),
+    };
 
     let skip_text = d
         .last_skip
@@ -32,7 +170,7 @@ pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
         .map(|ts| format!(" skip:{}", format_elapsed_time(ts)))
         .unwrap_or_default();
 
-    let line = Line::from(vec![
+    let mut spans = vec![
         Span::styled(
             format!(" {} ", daemon_icon),
             Style::default().fg(daemon_color),
@@ -43,9 +181,37 @@ pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
                 .fg(swarm_color)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(slots, Style::default().fg(Color::Cyan)),
+        Span::styled(slots, Style::default().fg(slots_color)),
         Span::styled(skip_text, Style::default().fg(Color::DarkGray)),
-    ]);
+    ];
+
+    match &app_state.replay {
+        Some(replay) => spans.extend(replay_spans(replay)),
+        None => {
+            if app_state.is_multi_instance() {
+                spans.extend(instance_spans(app_state));
+            }
+            spans.push(Span::styled(
+                format!(" {}", source_label),
+                Style::default().fg(source_color),
+            ));
+            spans.extend(connection_spans(app_state));
+        }
+    }
+
+    if let Some(toast) = app_state.active_toast() {
+        let (icon, color) = match toast.kind {
+            ToastKind::Pending => ("…", Color::Yellow),
+            ToastKind::Success => ("✓", Color::Green),
+            ToastKind::Error => ("✗", Color::Red),
+        };
+        spans.push(Span::styled(
+            format!("  {} {}", icon, toast.message),
+            Style::default().fg(color),
+        ));
+    }
+
+    let line = Line::from(spans);
 
     let widget = Paragraph::new(line).style(Style::default().fg(Color::White));
     frame.render_widget(widget, area);
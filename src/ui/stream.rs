@@ -8,6 +8,8 @@ use ratatui::{
 
 use crate::app::AppState;
 use crate::schema::TailEntry;
+use crate::search::Pane;
+use crate::source::Endpoint;
 
 fn format_entry<'a>(entry: &'a TailEntry) -> Vec<Span<'a>> {
     let spawn_short = &entry.spawn[..entry.spawn.len().min(8)];
@@ -86,14 +88,22 @@ fn stream_title(app_state: &AppState, count: usize) -> String {
 
 pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
     let count = app_state.stream.len();
-    let title = stream_title(app_state, count);
+    let title = format!(
+        "{}{}",
+        stream_title(app_state, count),
+        super::stale_tag(app_state, Endpoint::Tail)
+    );
 
     let items: Vec<ListItem> = app_state
         .stream
         .iter()
+        .enumerate()
         .skip(app_state.stream_scroll_offset)
         .take(area.height.saturating_sub(2) as usize)
-        .map(|entry| ListItem::new(Line::from(format_entry(entry))))
+        .map(|(idx, entry)| {
+            let line = Line::from(format_entry(entry));
+            ListItem::new(super::search_marks(app_state, Pane::Stream, idx, line))
+        })
         .collect();
 
     let list = List::new(items)
@@ -0,0 +1,359 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use regex::RegexBuilder;
+
+use crate::app::{AppState, Transcript, is_foldable};
+use crate::schema::Activity;
+
+const TIME_SLICE_START: usize = 11;
+const TIME_SLICE_END: usize = 19;
+const FOLD_PREVIEW: usize = 60;
+
+pub(crate) fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > width {
+            if current_len > 0 {
+                lines.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+            lines.push(word.drain(..width).collect());
+        }
+        if word.is_empty() {
+            continue;
+        }
+
+        let needed = if current_len == 0 {
+            word.len()
+        } else {
+            current_len + 1 + word.len()
+        };
+        if needed > width {
+            lines.push(std::mem::take(&mut current));
+            current_len = 0;
+        }
+        if current_len > 0 {
+            current.push(' ');
+            current_len += 1;
+        }
+        current.extend(word.iter());
+        current_len += word.len();
+    }
+
+    if current_len > 0 || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn hard_wrap(text: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars
+        .chunks(width.max(1))
+        .map(|c| c.iter().collect())
+        .collect()
+}
+
+fn list_marker(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(bullet) {
+            return Some(("• ".to_string(), rest));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(rest) = rest.strip_prefix(". ") {
+            return Some((format!("{}. ", &line[..digits]), rest));
+        }
+    }
+    None
+}
+
+pub(crate) fn render_markdown(text: &str, width: usize, base: Style) -> Vec<(String, Style)> {
+    let code = Style::default().fg(Color::Green);
+    let heading = Style::default()
+        .fg(Color::Magenta)
+        .add_modifier(Modifier::BOLD);
+    let quote = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::ITALIC);
+
+    let mut out = Vec::new();
+    let mut in_code = false;
+
+    for raw in text.lines() {
+        let trimmed = raw.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            let lang = trimmed.trim_start_matches('`').trim();
+            if in_code && !lang.is_empty() {
+                out.push((format!("┌ {}", lang), code));
+            }
+            continue;
+        }
+        if in_code {
+            for chunk in hard_wrap(raw, width.saturating_sub(2)) {
+                out.push((format!("│ {}", chunk), code));
+            }
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            out.push((String::new(), base));
+        } else if trimmed.starts_with('#') {
+            let title = trimmed.trim_start_matches('#').trim();
+            for chunk in wrap(title, width) {
+                out.push((chunk, heading));
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("> ") {
+            for chunk in wrap(rest, width.saturating_sub(2)) {
+                out.push((format!("│ {}", chunk), quote));
+            }
+        } else if let Some((marker, rest)) = list_marker(trimmed) {
+            let indent = raw.len() - trimmed.len();
+            let hang = indent + marker.chars().count();
+            for (i, chunk) in wrap(rest, width.saturating_sub(hang))
+                .into_iter()
+                .enumerate()
+            {
+                let lead = if i == 0 {
+                    format!("{}{}", " ".repeat(indent), marker)
+                } else {
+                    " ".repeat(hang)
+                };
+                out.push((format!("{}{}", lead, chunk), base));
+            }
+        } else {
+            for chunk in wrap(trimmed, width) {
+                out.push((chunk, base));
+            }
+        }
+    }
+    out
+}
+
+fn highlight(text: String, style: Style, query: &str) -> Vec<Span<'static>> {
+    if query.is_empty() {
+        return vec![Span::styled(text, style)];
+    }
+    let hit = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let Ok(re) = RegexBuilder::new(&regex::escape(query))
+        .case_insensitive(true)
+        .build()
+    else {
+        return vec![Span::styled(text, style)];
+    };
+
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for found in re.find_iter(&text) {
+        if found.start() > pos {
+            spans.push(Span::styled(text[pos..found.start()].to_string(), style));
+        }
+        spans.push(Span::styled(found.as_str().to_string(), hit));
+        pos = found.end();
+    }
+    if pos < text.len() {
+        spans.push(Span::styled(text[pos..].to_string(), style));
+    }
+    spans
+}
+
+fn event_style(kind: &str) -> Style {
+    Style::default().fg(match kind {
+        "tool_call" | "tool" => Color::Yellow,
+        "tool_result" | "result" => Color::Green,
+        "error" => Color::Red,
+        _ => Color::White,
+    })
+}
+
+fn event_lines(act: &Activity, idx: usize, view: &Transcript, width: usize) -> Vec<Line<'static>> {
+    let is_cursor = idx == view.cursor;
+    let gutter = if is_cursor { "▌" } else { " " };
+    let gutter_style = Style::default().fg(Color::Cyan);
+    let body_width = width.saturating_sub(3);
+    let style = event_style(&act.primitive);
+    let query = view.search.as_str();
+
+    let timestamp = act
+        .created_at
+        .get(TIME_SLICE_START..TIME_SLICE_END)
+        .unwrap_or("??:??:??");
+    let mut head = vec![
+        Span::styled(gutter, gutter_style),
+        Span::styled(
+            format!(" {} ", timestamp),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    head.extend(highlight(
+        act.primitive.clone(),
+        style.add_modifier(Modifier::BOLD),
+        query,
+    ));
+    if let Some(name) = &act.field {
+        head.push(Span::raw(" "));
+        head.extend(highlight(
+            name.clone(),
+            Style::default().fg(Color::Yellow),
+            query,
+        ));
+    }
+
+    let mut lines = vec![Line::from(head)];
+    let Some(body) = act.after.as_deref() else {
+        return lines;
+    };
+
+    let body_lines: Vec<(String, Style)> = if is_foldable(act) {
+        if act.id.is_some_and(|id| view.expanded.contains(&id)) {
+            body.lines()
+                .flat_map(|l| hard_wrap(l, body_width))
+                .map(|l| (l, style))
+                .collect()
+        } else {
+            let first = body.lines().next().unwrap_or("");
+            let preview: String = first.chars().take(FOLD_PREVIEW).collect();
+            let more = body.lines().count();
+            vec![(
+                format!("▸ {} ({} lines)", preview, more),
+                Style::default().fg(Color::DarkGray),
+            )]
+        }
+    } else {
+        render_markdown(body, body_width, style)
+    };
+
+    for (text, style) in body_lines {
+        let mut spans = vec![Span::styled(gutter, gutter_style), Span::raw("  ")];
+        spans.extend(highlight(text, style, query));
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+pub fn render(frame: &mut Frame, app_state: &AppState, area: Rect) {
+    let Some(view) = app_state.transcript.as_ref() else {
+        return;
+    };
+
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let height = area.height.saturating_sub(2) as usize;
+
+    let mut lines = Vec::new();
+    let mut cursor_start = 0;
+    for (idx, act) in app_state.spawn_activity.iter().enumerate() {
+        if idx == view.cursor {
+            cursor_start = lines.len();
+        }
+        lines.extend(event_lines(act, idx, view, inner_width));
+    }
+
+    let start = (cursor_start + view.line_offset).min(lines.len().saturating_sub(1));
+    let visible: Vec<Line> = lines.into_iter().skip(start).take(height).collect();
+
+    let identity = app_state
+        .selected_spawn()
+        .map(|s| app_state.resolve_identity(&s.agent_id))
+        .unwrap_or("?");
+    let title = format!(
+        "Transcript {} {} ({}/{})",
+        &view.spawn_id[..view.spawn_id.len().min(8)],
+        identity,
+        (view.cursor + 1).min(app_state.spawn_activity.len()),
+        app_state.spawn_activity.len()
+    );
+
+    let footer = if view.searching {
+        format!(" /{}▏", view.search)
+    } else if !view.search.is_empty() {
+        format!(
+            " /{} — {} matches (n/N) ",
+            view.search,
+            app_state.transcript_matches().len()
+        )
+    } else {
+        " j/k event  J/K scroll  o fold  / search  g/G ends  esc close ".to_string()
+    };
+
+    let widget = Paragraph::new(visible).block(
+        Block::default()
+            .title(title)
+            .title_alignment(Alignment::Left)
+            .title_bottom(Line::from(Span::styled(
+                footer,
+                Style::default().fg(Color::DarkGray),
+            )))
+            .borders(Borders::ALL),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(widget, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_breaks_on_words() {
+        assert_eq!(
+            wrap("the quick brown fox jumps", 10),
+            vec!["the quick", "brown fox", "jumps"]
+        );
+    }
+
+    #[test]
+    fn wrap_splits_long_words() {
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn markdown_renders_headings_lists_and_code() {
+        let text = "# Plan\n- first item\n```rust\nlet x = 1;\n```\nafter";
+        let rendered: Vec<String> = render_markdown(text, 40, Style::default())
+            .into_iter()
+            .map(|(l, _)| l)
+            .collect();
+        assert_eq!(
+            rendered,
+            vec!["Plan", "• first item", "┌ rust", "│ let x = 1;", "after"]
+        );
+    }
+
+    #[test]
+    fn list_items_hang_indent() {
+        let rendered: Vec<String> = render_markdown("- alpha beta gamma", 10, Style::default())
+            .into_iter()
+            .map(|(l, _)| l)
+            .collect();
+        assert_eq!(rendered, vec!["• alpha", "  beta", "  gamma"]);
+    }
+
+    #[test]
+    fn highlight_marks_case_insensitive_hits() {
+        let spans = highlight("Run cargo TEST now".to_string(), Style::default(), "test");
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["Run cargo ", "TEST", " now"]);
+
+        // Lowercasing İ changes its byte length; hits still land on the original text.
+        let spans = highlight("İSTANBUL test".to_string(), Style::default(), "TEST");
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["İSTANBUL ", "test"]);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::schema::{Activity, Agent, Spawn};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+// space-os response payloads. Required fields fail decoding when absent, so schema
+// drift surfaces as an error instead of empty ids. Keep in sync with the lists in `contract`.
+
+// Decodes a list row by row: a row that doesn't fit the payload is dropped and
+// described instead of failing the whole response.
+pub fn decode_rows<P: DeserializeOwned>(values: Vec<Value>) -> (Vec<P>, Vec<String>) {
+    let (rows, rejected) = decode_indexed_rows(values);
+    (rows.into_iter().map(|(_, row)| row).collect(), rejected)
+}
+
+// Like `decode_rows`, keeping each row's position in the response.
+pub fn decode_indexed_rows<P: DeserializeOwned>(
+    values: Vec<Value>,
+) -> (Vec<(usize, P)>, Vec<String>) {
+    let mut rows = Vec::new();
+    let mut rejected = Vec::new();
+    for (i, value) in values.into_iter().enumerate() {
+        match serde_json::from_value(value) {
+            Ok(row) => rows.push((i, row)),
+            Err(e) => rejected.push(format!("row {}: {}", i, e)),
+        }
+    }
+    (rows, rejected)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgentPayload {
+    pub id: String,
+    pub identity: String,
+    #[serde(rename = "type")]
+    pub agent_type: String,
+    pub created_at: String,
+    pub model: Option<String>,
+    pub constitution: Option<String>,
+    pub avatar_path: Option<String>,
+    pub color: Option<String>,
+    pub archived_at: Option<String>,
+}
+
+impl From<AgentPayload> for Agent {
+    fn from(p: AgentPayload) -> Self {
+        Agent {
+            id: p.id,
+            identity: p.identity,
+            agent_type: p.agent_type,
+            model: p.model,
+            constitution: p.constitution,
+            avatar_path: p.avatar_path,
+            color: p.color,
+            created_at: p.created_at,
+            archived_at: p.archived_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpawnPayload {
+    pub id: String,
+    pub agent_id: String,
+    pub status: String,
+    pub created_at: String,
+    pub project_id: Option<String>,
+    pub caller_spawn_id: Option<String>,
+    pub source: Option<String>,
+    pub error: Option<String>,
+    pub pid: Option<i32>,
+    pub session_id: Option<String>,
+    pub summary: Option<String>,
+    pub trace_hash: Option<String>,
+    pub last_active_at: Option<String>,
+}
+
+impl From<SpawnPayload> for Spawn {
+    fn from(p: SpawnPayload) -> Self {
+        Spawn {
+            id: p.id,
+            agent_id: p.agent_id,
+            project_id: p.project_id,
+            caller_spawn_id: p.caller_spawn_id,
+            source: p.source,
+            status: p.status,
+            error: p.error,
+            pid: p.pid,
+            session_id: p.session_id,
+            summary: p.summary,
+            trace_hash: p.trace_hash,
+            created_at: p.created_at,
+            last_active_at: p.last_active_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LedgerPayload {
+    pub id: String,
+    pub agent_id: String,
+    #[serde(rename = "type")]
+    pub primitive: String,
+    pub created_at: String,
+    pub status: Option<String>,
+    pub content: Option<String>,
+}
+
+impl From<LedgerPayload> for Activity {
+    fn from(p: LedgerPayload) -> Self {
+        Activity {
+            id: None,
+            agent_id: p.agent_id,
+            spawn_id: None,
+            primitive: p.primitive,
+            primitive_id: p.id,
+            action: p.status.unwrap_or_else(|| "created".to_string()),
+            field: None,
+            after: p.content,
+            created_at: p.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpawnEventsPayload {
+    pub events: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpawnEventPayload {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub timestamp: String,
+    pub agent_id: Option<String>,
+    pub name: Option<String>,
+    pub content: Option<String>,
+    pub args: Option<serde_json::Value>,
+}
+
+impl SpawnEventPayload {
+    // Events carry no id or sequence, so (spawn_id, `index` in the response) stands in
+    // for one. It holds while the spawn has fewer events than the fetch limit; past
+    // that the window slides and indices shift.
+    pub fn into_activity(self, index: usize, spawn_id: &str) -> Activity {
+        let args = self.args.and_then(|args| match args {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(s) => Some(s),
+            other => Some(other.to_string()),
+        });
+        Activity {
+            id: Some(index as i64),
+            agent_id: self.agent_id.unwrap_or_default(),
+            spawn_id: Some(spawn_id.to_string()),
+            primitive: self.kind.clone(),
+            primitive_id: spawn_id.to_string(),
+            action: self.kind,
+            field: self.name,
+            after: self.content.or(args),
+            created_at: self.timestamp,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use space_cmd::app::{AppState, ToastKind};
+use space_cmd::command::{Command, parse};
+use space_cmd::schema::Spawn;
+
+fn spawn(id: &str, status: &str, error: Option<&str>) -> Spawn {
+    Spawn {
+        id: id.to_string(),
+        agent_id: "a1".to_string(),
+        status: status.to_string(),
+        error: error.map(String::from),
+        pid: Some(4242),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn kill_requires_confirmation() {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("spawn-active", "active", None)];
+
+    state.request_kill();
+    let confirm = state.confirm.clone().expect("confirm prompt");
+    assert!(confirm.prompt.contains("pid 4242"));
+
+    assert_eq!(
+        state.take_confirmed(),
+        Some(Command::Kill("spawn-active".to_string()))
+    );
+    assert!(state.confirm.is_none());
+}
+
+#[test]
+fn cancelled_kill_dispatches_nothing() {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("spawn-active", "active", None)];
+    state.request_kill();
+    state.cancel_confirm();
+    assert_eq!(state.take_confirmed(), None);
+}
+
+#[test]
+fn typed_kill_waits_for_the_same_confirmation() {
+    let mut state = AppState::new();
+    state.spawns = vec![
+        spawn("spawn-other", "active", None),
+        spawn("spawn-active", "active", None),
+    ];
+    state.request_kill_spawn("spawn-active");
+    let confirm = state.confirm.clone().expect("confirm prompt");
+    assert!(confirm.prompt.contains("spawn-ac"));
+    assert_eq!(
+        state.take_confirmed(),
+        Some(Command::Kill("spawn-active".to_string()))
+    );
+
+    state.spawns[1].status = "done".to_string();
+    state.request_kill_spawn("spawn-active");
+    assert!(state.confirm.is_none());
+}
+
+#[test]
+fn kill_refuses_finished_spawn() {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("spawn-done", "done", None)];
+    state.request_kill();
+    assert!(state.confirm.is_none());
+    assert_eq!(state.active_toast().map(|t| t.kind), Some(ToastKind::Error));
+}
+
+#[test]
+fn retry_only_for_failed_spawns() {
+    let mut state = AppState::new();
+    state.spawns = vec![
+        spawn("spawn-ok", "done", None),
+        spawn("spawn-bad", "done", Some("exit 1")),
+    ];
+    assert_eq!(state.request_retry(), None);
+
+    state.active_spawn_idx = 1;
+    assert_eq!(
+        state.request_retry(),
+        Some(Command::Retry("spawn-bad".to_string()))
+    );
+    assert_eq!(parse(":retry abc"), Ok(Command::Retry("abc".to_string())));
+}
+
+#[test]
+fn follow_up_flag_toggles() {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("spawn-1", "active", None)];
+    state.toggle_follow_up();
+    assert!(state.flagged_spawns.contains("spawn-1"));
+    state.toggle_follow_up();
+    assert!(state.flagged_spawns.is_empty());
+}
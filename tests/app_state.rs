@@ -1,4 +1,4 @@
-use space_cmd::app::{AppState, SidebarTab};
+use space_cmd::app::{AppState, RightPane, SidebarTab};
 use space_cmd::schema::{Activity, Agent, Spawn};
 
 #[test]
@@ -95,7 +95,7 @@ fn activity_scroll_respects_bounds() {
     let mut state = AppState::new();
     state.activity = vec![
         Activity {
-            id: 1,
+            id: Some(1),
             agent_id: "a1".to_string(),
             spawn_id: None,
             primitive: "spawn".to_string(),
@@ -106,7 +106,7 @@ fn activity_scroll_respects_bounds() {
             created_at: "2026-02-05T10:00:00Z".to_string(),
         },
         Activity {
-            id: 2,
+            id: Some(2),
             agent_id: "a1".to_string(),
             spawn_id: None,
             primitive: "spawn".to_string(),
@@ -128,3 +128,133 @@ fn activity_scroll_respects_bounds() {
     state.scroll_activity_up();
     assert_eq!(state.activity_scroll_offset, 0);
 }
+
+#[test]
+fn selecting_spawn_opens_detail_pane() {
+    let mut state = AppState::new();
+    state.spawns = vec![Spawn {
+        id: "spawn1".to_string(),
+        agent_id: "a1".to_string(),
+        project_id: None,
+        caller_spawn_id: None,
+        source: None,
+        status: "active".to_string(),
+        error: None,
+        pid: None,
+        session_id: None,
+        summary: None,
+        trace_hash: None,
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        last_active_at: None,
+    }];
+
+    state.next_spawn_global();
+    assert_eq!(state.right_pane, RightPane::SpawnDetail);
+    assert_eq!(
+        state.selected_spawn().map(|s| s.id.as_str()),
+        Some("spawn1")
+    );
+}
+
+#[test]
+fn right_pane_cycles_through_spawn_detail() {
+    let mut state = AppState::new();
+    assert_eq!(state.right_pane, RightPane::Stream);
+    state.toggle_right_pane();
+    assert_eq!(state.right_pane, RightPane::Ledger);
+    state.toggle_right_pane();
+    assert_eq!(state.right_pane, RightPane::SpawnDetail);
+    state.toggle_right_pane();
+    assert_eq!(state.right_pane, RightPane::Stream);
+}
+
+fn activity_at(id: &str, created_at: &str) -> Activity {
+    Activity {
+        id: None,
+        agent_id: "a1".to_string(),
+        spawn_id: None,
+        primitive: "decision".to_string(),
+        primitive_id: id.to_string(),
+        action: "created".to_string(),
+        field: None,
+        after: None,
+        created_at: created_at.to_string(),
+    }
+}
+
+#[test]
+fn scrolling_past_end_pages_older_activity() {
+    let mut state = AppState::new();
+    state.all_stream = true;
+    state.set_activity(
+        None,
+        vec![
+            activity_at("n2", "2026-01-01T00:02:00Z"),
+            activity_at("n1", "2026-01-01T00:01:00Z"),
+        ],
+    );
+
+    state.scroll_activity_down();
+    assert!(state.take_older_activity_query().is_none());
+    state.scroll_activity_down();
+    let query = state.take_older_activity_query().expect("page query");
+    assert_eq!(query.before.as_deref(), Some("2026-01-01T00:01:00Z"));
+    assert_eq!(query.agent_id, None);
+
+    state.append_activity_page(None, vec![activity_at("o1", "2026-01-01T00:00:30Z")]);
+    assert_eq!(state.activity.len(), 3);
+
+    // A refresh keeps the older page behind the fresh window.
+    state.set_activity(
+        None,
+        vec![
+            activity_at("n3", "2026-01-01T00:03:00Z"),
+            activity_at("n2", "2026-01-01T00:02:00Z"),
+        ],
+    );
+    let ids: Vec<&str> = state
+        .activity
+        .iter()
+        .map(|a| a.primitive_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["n3", "n2", "n1", "o1"]);
+
+    state.append_activity_page(None, vec![]);
+    state.scroll_activity_down();
+    state.scroll_activity_down();
+    assert!(state.take_older_activity_query().is_none());
+}
+
+#[test]
+fn older_page_skips_rows_repeated_at_the_inclusive_boundary() {
+    let mut state = AppState::new();
+    state.set_activity(
+        None,
+        vec![
+            activity_at("n2", "2026-01-01T00:02:00Z"),
+            activity_at("n1", "2026-01-01T00:01:00Z"),
+        ],
+    );
+
+    state.append_activity_page(
+        None,
+        vec![
+            activity_at("n1", "2026-01-01T00:01:00Z"),
+            activity_at("m1", "2026-01-01T00:01:00Z"),
+        ],
+    );
+    let ids: Vec<&str> = state
+        .activity
+        .iter()
+        .map(|a| a.primitive_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["n2", "n1", "m1"]);
+
+    // Nothing but repeats: there is nothing older.
+    state.append_activity_page(None, vec![activity_at("m1", "2026-01-01T00:01:00Z")]);
+    assert_eq!(state.activity.len(), 3);
+    state.scroll_activity_down();
+    state.scroll_activity_down();
+    state.scroll_activity_down();
+    assert!(state.take_older_activity_query().is_none());
+}
@@ -0,0 +1,137 @@
+use space_cmd::api::{ApiClient, ApiError};
+use space_cmd::app::AppState;
+#[cfg(feature = "mock")]
+use space_cmd::command;
+use space_cmd::config::{AuthScheme, Config, Instance, Secret};
+#[cfg(feature = "mock")]
+use space_cmd::mock::{self, Scenario};
+#[cfg(feature = "mock")]
+use space_cmd::schema::LedgerQuery;
+use space_cmd::source::{Endpoint, EndpointStats};
+#[cfg(feature = "mock")]
+use tokio::net::TcpListener;
+
+#[cfg(feature = "mock")]
+async fn start(token: &str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(mock::serve(
+        listener,
+        Scenario {
+            token: Some(token.to_string()),
+            ..Scenario::default()
+        },
+    ));
+    format!("http://{}", addr)
+}
+
+fn instance(url: &str, token: &str) -> Instance {
+    Instance {
+        token: Some(Secret::new(token)),
+        ..Instance::new("team", url)
+    }
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn bearer_token_is_sent_on_reads() {
+    let url = start("s3cret").await;
+
+    let client = ApiClient::connect(&instance(&url, "s3cret")).expect("client");
+    assert!(client.get_agents().await.is_ok());
+
+    let anonymous = ApiClient::new(url.clone()).expect("client");
+    let err = anonymous.get_agents().await.expect_err("rejected");
+    assert!(matches!(err, ApiError::Unauthorized(401)));
+    assert!(err.is_auth());
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn api_key_scheme_uses_its_own_header() {
+    let url = start("k3y").await;
+    let client = ApiClient::connect(&Instance {
+        auth: AuthScheme::ApiKey,
+        ..instance(&url, "k3y")
+    })
+    .expect("client");
+    assert!(client.get_daemon_status().await.is_ok());
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn identity_is_attached_to_writes() {
+    let url = start("s3cret").await;
+    let client = ApiClient::connect(&Instance {
+        identity: Some("operator".to_string()),
+        ..instance(&url, "s3cret")
+    })
+    .expect("client");
+
+    command::create_task(&client, "rotate keys")
+        .await
+        .expect("task");
+    let ledger = client
+        .get_ledger(&LedgerQuery::new(10))
+        .await
+        .expect("ledger");
+    assert_eq!(ledger[0].agent_id, "operator");
+}
+
+#[test]
+fn tokens_never_show_in_debug_output() {
+    let instance = instance("http://box:8228", "s3cret");
+    assert!(!format!("{:?}", instance).contains("s3cret"));
+    let client = ApiClient::connect(&instance).expect("client");
+    assert!(!format!("{:?}", client).contains("s3cret"));
+}
+
+#[test]
+fn config_reads_auth_settings() {
+    let config = Config::parse(
+        r#"
+[[instances]]
+name = "team"
+url = "https://space.team"
+auth = "api-key"
+token_env = "SPACE_TEAM_TOKEN"
+identity = "operator"
+ca_cert = "/etc/ssl/team-ca.pem"
+"#,
+    )
+    .expect("parse");
+    let team = &config.instances[0];
+    assert_eq!(team.auth, AuthScheme::ApiKey);
+    assert_eq!(team.token_env.as_deref(), Some("SPACE_TEAM_TOKEN"));
+    assert_eq!(team.identity.as_deref(), Some("operator"));
+}
+
+#[test]
+fn missing_ca_cert_fails_to_connect() {
+    let instance = Instance {
+        ca_cert: Some("/nonexistent/space-ca.pem".into()),
+        ..Instance::new("team", "https://space.team")
+    };
+    assert!(ApiClient::connect(&instance).is_err());
+}
+
+#[test]
+fn auth_failures_outrank_other_errors() {
+    let stats = |endpoint, failures, error| EndpointStats {
+        endpoint,
+        last_success: None,
+        last_error: Some(error),
+        latency_ms: None,
+        consecutive_failures: failures,
+        rejected: Vec::new(),
+    };
+    let mut state = AppState::new();
+    state.endpoints = vec![
+        stats(Endpoint::Tail, 5, ApiError::Network("timeout".to_string())),
+        stats(Endpoint::Agents, 1, ApiError::Unauthorized(403)),
+    ];
+
+    assert!(state.is_unauthorized());
+    let worst = state.worst_endpoint().expect("worst");
+    assert_eq!(worst.endpoint, Endpoint::Agents);
+}
@@ -1,4 +1,40 @@
 use space_cmd::app::{AppState, AutocompleteMode};
+use space_cmd::fuzzy;
+use space_cmd::schema::{Activity, Agent, Spawn};
+
+fn agent(id: &str, identity: &str, agent_type: &str) -> Agent {
+    Agent {
+        id: id.to_string(),
+        identity: identity.to_string(),
+        agent_type: agent_type.to_string(),
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+fn spawn(id: &str, agent_id: &str, status: &str) -> Spawn {
+    Spawn {
+        id: id.to_string(),
+        agent_id: agent_id.to_string(),
+        status: status.to_string(),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+fn ledger_item(id: &str, primitive: &str, content: &str) -> Activity {
+    Activity {
+        id: None,
+        agent_id: "a1".to_string(),
+        spawn_id: None,
+        primitive: primitive.to_string(),
+        primitive_id: id.to_string(),
+        action: "created".to_string(),
+        field: None,
+        after: Some(content.to_string()),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+    }
+}
 
 #[test]
 fn agent_trigger_at_word_start() {
@@ -39,6 +75,17 @@ fn file_trigger_with_character() {
     assert_eq!(state.autocomplete_query, "src");
 }
 
+#[test]
+fn bare_relative_paths_complete() {
+    let mut state = AppState::new();
+    state.input_text = "see src/ma".to_string();
+    state.detect_and_trigger_autocomplete();
+
+    assert_eq!(state.autocomplete_mode, Some(AutocompleteMode::File));
+    assert_eq!(state.autocomplete_query, "ma");
+    assert!(state.autocomplete_list.iter().any(|f| f == "main.rs"));
+}
+
 #[test]
 fn filter_matches_substring() {
     let mut state = AppState::new();
@@ -134,3 +181,116 @@ fn cancel_clears_all_state() {
     assert_eq!(state.autocomplete_idx, 0);
     assert!(state.autocomplete_query.is_empty());
 }
+
+#[test]
+fn fuzzy_ranks_prefix_and_boundary_matches_first() {
+    assert!(fuzzy::find("zlt", "zealot").is_some());
+    assert!(fuzzy::find("tlz", "zealot").is_none());
+    assert_eq!(
+        fuzzy::find("ZEA", "zealot").unwrap().positions,
+        vec![0, 1, 2]
+    );
+
+    let mut state = AppState::new();
+    state.autocomplete_list = ["mezzanine", "fix-zealot", "zealot", "z-e-a"]
+        .map(String::from)
+        .to_vec();
+    state.autocomplete_query = "zea".to_string();
+    state.filter_autocomplete();
+    assert_eq!(state.autocomplete_list, ["zealot", "fix-zealot", "z-e-a"]);
+}
+
+#[test]
+fn agent_completion_uses_known_agents_with_icon_and_load() {
+    let mut state = AppState::new();
+    state.agents = vec![
+        agent("a1", "hailot", "ai"),
+        agent("a2", "human", "human"),
+        agent("a3", "sentinel", "ai"),
+    ];
+    state.spawns = vec![
+        spawn("s1", "a1", "active"),
+        spawn("s2", "a1", "active"),
+        spawn("s3", "a1", "done"),
+    ];
+    state.input_text = "ping @hl".to_string();
+    state.detect_and_trigger_autocomplete();
+
+    assert!(state.is_completing());
+    assert_eq!(state.autocomplete_list, ["hailot"]);
+    let hint = &state.autocomplete_hints["hailot"];
+    assert_eq!((hint.icon, hint.detail.as_str()), ("~", "2 active"));
+
+    state.autocomplete_select();
+    assert_eq!(state.input_text, "ping @hailot ");
+    assert!(!state.is_completing());
+}
+
+#[test]
+fn hash_completes_spawn_ids_by_id_or_agent() {
+    let mut state = AppState::new();
+    for (id, identity) in [("a1", "zealot"), ("a2", "sentinel")] {
+        state.agents.push(agent(id, identity, "ai"));
+        state
+            .agent_identities
+            .insert(id.to_string(), identity.to_string());
+    }
+    state.spawns = vec![
+        spawn("abcdef123456", "a1", "active"),
+        spawn("99887766aabb", "a2", "done"),
+    ];
+    state.input_text = "look at #sent".to_string();
+    state.detect_and_trigger_autocomplete();
+
+    assert_eq!(state.autocomplete_mode, Some(AutocompleteMode::Spawn));
+    assert_eq!(state.autocomplete_list, ["99887766"]);
+    state.autocomplete_select();
+    assert_eq!(state.input_text, "look at #99887766 ");
+}
+
+#[test]
+fn ledger_triggers_match_item_text_and_insert_ids() {
+    let mut state = AppState::new();
+    state.ledger = vec![
+        ledger_item("task0001xyz", "task", "migrate the cache format"),
+        ledger_item("dec00001xyz", "decision", "use sqlite for the cache"),
+        ledger_item("task0002xyz", "task", "write release notes"),
+    ];
+    state.input_text = "see t/cache".to_string();
+    state.detect_and_trigger_autocomplete();
+
+    assert_eq!(state.autocomplete_mode, Some(AutocompleteMode::Ledger));
+    assert_eq!(state.autocomplete_list, ["task0001"]);
+    state.autocomplete_select();
+    assert_eq!(state.input_text, "see t/task0001 ");
+
+    state.input_text = "d/".to_string();
+    state.detect_and_trigger_autocomplete();
+    assert_eq!(state.autocomplete_list, ["dec00001"]);
+}
+
+#[test]
+fn path_completion_walks_directories() {
+    let root = std::env::temp_dir().join(format!("space-cmd-paths-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("notes")).unwrap();
+    std::fs::write(root.join("notes").join("plan.md"), "").unwrap();
+    std::fs::write(root.join("nothing.txt"), "").unwrap();
+    std::fs::write(root.join(".hidden"), "").unwrap();
+
+    let mut state = AppState::new();
+    state.input_text = format!("read {}/n", root.display());
+    state.detect_and_trigger_autocomplete();
+    assert_eq!(state.autocomplete_mode, Some(AutocompleteMode::File));
+    assert_eq!(state.autocomplete_list, ["notes/", "nothing.txt"]);
+
+    // A directory completes without a space and lists what's inside.
+    state.autocomplete_select();
+    assert_eq!(state.input_text, format!("read {}/notes/", root.display()));
+    assert_eq!(state.autocomplete_list, ["plan.md"]);
+    state.autocomplete_select();
+    let _ = std::fs::remove_dir_all(&root);
+    assert_eq!(
+        state.input_text,
+        format!("read {}/notes/plan.md ", root.display())
+    );
+}
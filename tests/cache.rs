@@ -0,0 +1,62 @@
+use space_cmd::app::AppState;
+use space_cmd::cache::{self, Snapshot};
+use space_cmd::schema::Agent;
+use space_cmd::source::{Endpoint, EndpointStats};
+
+fn agent(id: &str, identity: &str) -> Agent {
+    Agent {
+        id: id.to_string(),
+        identity: identity.to_string(),
+        agent_type: "ai".to_string(),
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn snapshot_round_trips_through_disk() {
+    let mut state = AppState::new();
+    state.agents = vec![agent("a1", "zealot")];
+    state
+        .agent_identities
+        .insert("a1".to_string(), "zealot".to_string());
+
+    let path = std::env::temp_dir()
+        .join(format!("space-cmd-cache-{}", std::process::id()))
+        .join("snapshot.json");
+    cache::save(&path, &Snapshot::capture(&state)).expect("save cache");
+    let loaded = cache::load(&path).expect("load cache");
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+    let mut restored = AppState::new();
+    loaded.restore(&mut restored);
+    assert_eq!(restored.agents.len(), 1);
+    assert_eq!(restored.resolve_identity("a1"), "zealot");
+    assert!(restored.cached_at.is_some());
+}
+
+#[test]
+fn cached_panes_are_stale_until_endpoint_answers() {
+    let mut state = AppState::new();
+    assert!(!state.is_stale(Endpoint::Agents));
+
+    Snapshot::capture(&state).restore(&mut state);
+    assert!(state.is_stale(Endpoint::Agents));
+
+    state.endpoints = vec![EndpointStats {
+        endpoint: Endpoint::Agents,
+        last_success: Some(chrono::Utc::now()),
+        last_error: None,
+        latency_ms: Some(5),
+        consecutive_failures: 0,
+        rejected: Vec::new(),
+    }];
+    assert!(!state.is_stale(Endpoint::Agents));
+    assert!(state.is_stale(Endpoint::Ledger));
+}
+
+#[test]
+fn missing_cache_loads_nothing() {
+    let path = std::env::temp_dir().join("space-cmd-no-such-cache.json");
+    assert!(cache::load(&path).is_none());
+}
@@ -0,0 +1,99 @@
+use space_cmd::app::{AppState, AutocompleteMode};
+use space_cmd::command::{Command, CommandError, parse};
+use space_cmd::schema::Spawn;
+
+fn spawn(id: &str, status: &str) -> Spawn {
+    Spawn {
+        id: id.to_string(),
+        agent_id: "a1".to_string(),
+        status: status.to_string(),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn parses_typed_commands() {
+    assert_eq!(
+        parse(":task fix the deploy"),
+        Ok(Command::Task("fix the deploy".to_string()))
+    );
+    assert_eq!(
+        parse(":kill abc123"),
+        Ok(Command::Kill("abc123".to_string()))
+    );
+    assert_eq!(parse(":pause"), Ok(Command::Pause));
+    assert_eq!(parse(":swarm off"), Ok(Command::Swarm(false)));
+    assert_eq!(parse(":concurrency 4"), Ok(Command::Concurrency(4)));
+    assert_eq!(
+        parse(":focus @zealot"),
+        Ok(Command::Focus("zealot".to_string()))
+    );
+}
+
+#[test]
+fn bad_arguments_report_usage() {
+    assert_eq!(parse(":task"), Err(CommandError::Usage(":task <content>")));
+    assert_eq!(
+        parse(":swarm maybe"),
+        Err(CommandError::Usage(":swarm on|off"))
+    );
+    assert_eq!(
+        parse(":concurrency many"),
+        Err(CommandError::Usage(":concurrency <n>"))
+    );
+    assert_eq!(
+        parse(":concurrency 0"),
+        Err(CommandError::Usage(":concurrency <n>"))
+    );
+    assert_eq!(parse(":pause now"), Err(CommandError::Usage(":pause")));
+}
+
+#[test]
+fn unknown_command_is_reported() {
+    assert_eq!(
+        parse(":launch"),
+        Err(CommandError::Unknown("launch".to_string()))
+    );
+}
+
+#[test]
+fn command_name_autocomplete() {
+    let mut state = AppState::new();
+    state.input_text = ":con".to_string();
+    state.detect_and_trigger_autocomplete();
+
+    assert_eq!(state.autocomplete_mode, Some(AutocompleteMode::Command));
+    assert_eq!(state.autocomplete_list, vec!["concurrency".to_string()]);
+
+    state.autocomplete_select();
+    assert_eq!(state.input_text, ":concurrency ");
+}
+
+#[test]
+fn kill_argument_completes_active_spawns() {
+    let mut state = AppState::new();
+    state.spawns = vec![
+        spawn("abcdef123456", "active"),
+        spawn("abzzzz999999", "done"),
+    ];
+    state.input_text = ":kill ab".to_string();
+    state.detect_and_trigger_autocomplete();
+
+    assert_eq!(state.autocomplete_mode, Some(AutocompleteMode::Argument));
+    assert_eq!(state.autocomplete_list, vec!["abcdef12".to_string()]);
+
+    state.autocomplete_select();
+    assert_eq!(state.input_text, ":kill abcdef12 ");
+    assert_eq!(
+        state.resolve_spawn_id("abcdef12"),
+        Some("abcdef123456".to_string())
+    );
+}
+
+#[test]
+fn ambiguous_spawn_prefix_does_not_resolve() {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("abc1", "active"), spawn("abc2", "active")];
+    assert_eq!(state.resolve_spawn_id("abc"), None);
+}
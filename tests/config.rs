@@ -0,0 +1,121 @@
+use space_cmd::app::AppState;
+use space_cmd::config::{Config, Instance, Secret};
+use space_cmd::poll::Resource;
+use std::time::Duration;
+
+fn set(spec: &str) -> Vec<String> {
+    vec![spec.to_string()]
+}
+
+#[test]
+fn empty_file_keeps_built_in_defaults() {
+    let config = Config::parse("").expect("parse");
+    assert_eq!(config.api.timeout_ms, 800);
+    assert_eq!(config.refresh.stream_ms, 500);
+    assert_eq!(config.limits.activity, 500);
+    assert_eq!(config.layout.activity, 50);
+    assert_eq!(config.health.stale_days, 7);
+}
+
+#[test]
+fn file_values_reach_poller_and_app_state() {
+    let config = Config::parse(
+        r#"
+[refresh]
+stream_ms = 250
+roster_ms = 5000
+
+[limits]
+activity = 100
+
+[layout]
+sidebar = 30
+activity = 40
+right = 30
+"#,
+    )
+    .expect("parse");
+    assert_eq!(
+        Resource::Stream.period(&config.refresh),
+        Duration::from_millis(250)
+    );
+    assert_eq!(
+        Resource::Daemon.period(&config.refresh),
+        Duration::from_secs(5)
+    );
+
+    let mut state = AppState::with_config(&config);
+    state.toggle_all_stream();
+    assert_eq!(state.layout.sidebar, 30);
+    assert_eq!(state.focus().activity.map(|q| q.limit), Some(100));
+}
+
+#[test]
+fn overrides_win_over_the_file() {
+    let raw = "[refresh]\nstream_ms = 250\n";
+    let config = Config::parse_with(raw, &set("refresh.stream_ms=1000")).expect("parse");
+    assert_eq!(config.refresh.stream_ms, 1000);
+
+    let config = Config::parse_with("", &set("api.url=http://box:8228")).expect("parse");
+    assert_eq!(config.api.url.as_deref(), Some("http://box:8228"));
+
+    assert!(Config::parse_with("", &set("refresh.stream_ms")).is_err());
+}
+
+#[test]
+fn invalid_values_are_explained() {
+    let err = |raw: &str| Config::parse(raw).expect_err("invalid").to_string();
+
+    assert!(err("[layout]\nsidebar = 50\n").contains("add up to 100"));
+    assert!(
+        err("[layout]\nsidebar = 65535\nactivity = 65535\nright = 2\n").contains("add up to 100")
+    );
+    assert!(err("[refresh]\nstream_ms = 10\n").contains("refresh.stream_ms"));
+    assert!(err("[refresh]\nstrem_ms = 250\n").contains("strem_ms"));
+    assert!(err("[limits]\nfetchh = 5\n").contains("fetchh"));
+    assert!(err("[layout]\nsidbar = 20\n").contains("sidbar"));
+    assert!(err("[health]\nstale = 3\n").contains("stale"));
+    assert!(
+        err("[[instances]]\nname = \"box\"\nurl = \"http://box:8228\"\ntimeout_ms = 10\n")
+            .contains("instance.box.timeout_ms")
+    );
+    assert!(err("[[instances]]\nname = \"a\"\nurl = \"box:8228\"\n").contains("http://"));
+    assert!(
+        err("[[instances]]\nname = \"../x\"\nurl = \"http://box:8228\"\n").contains("may only use")
+    );
+}
+
+#[test]
+fn instances_inherit_the_api_timeout() {
+    let config = Config {
+        instances: vec![
+            Instance::new("local", "http://localhost:8228"),
+            Instance {
+                timeout_ms: Some(3000),
+                ..Instance::new("remote", "https://box")
+            },
+        ],
+        ..Config::default()
+    };
+    let instances = config.instances();
+    assert_eq!(instances[0].timeout_ms, Some(800));
+    assert_eq!(instances[1].timeout_ms, Some(3000));
+}
+
+#[test]
+fn effective_config_round_trips_without_secrets() {
+    let config = Config {
+        instances: vec![Instance {
+            token: Some(Secret::new("s3cret")),
+            ..Instance::new("team", "https://space.team")
+        }],
+        ..Config::default()
+    };
+    let shown = toml::to_string_pretty(&config.effective()).expect("serialize");
+    assert!(!shown.contains("s3cret"));
+    assert!(shown.contains("[[instances]]"));
+
+    let reparsed = Config::parse(&shown).expect("reparse");
+    assert_eq!(reparsed.refresh, config.refresh);
+    assert_eq!(reparsed.instances[0].name, "team");
+}
@@ -0,0 +1,163 @@
+use serde_json::json;
+use space_cmd::api::ApiClient;
+use space_cmd::contract::{AGENTS, SPAWN_EVENTS};
+#[cfg(feature = "mock")]
+use space_cmd::mock::{self, Scenario};
+use space_cmd::schema::{Agent, LedgerQuery};
+use space_cmd::source::{Endpoint, Source};
+use space_cmd::wire::{self, AgentPayload};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+// Answers every request with `body`, for payloads the mock server won't produce.
+async fn serve_json(body: serde_json::Value) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    let body = body.to_string();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[test]
+fn reports_missing_and_unknown_fields() {
+    let report = AGENTS.check(json!([
+        { "id": "a1", "identity": "zealot", "type": "ai", "created_at": "2026-02-05T10:00:00Z" },
+        { "id": "a2", "identity": null, "type": "ai", "created_at": "2026-02-05T10:00:00Z", "mood": "calm" },
+    ]));
+    assert_eq!(report.items, 2);
+    assert!(report.missing.contains("identity"));
+    assert!(report.unknown.contains("mood"));
+    assert!(!report.is_ok());
+}
+
+#[test]
+fn unknown_fields_alone_are_not_a_break() {
+    let report = AGENTS.check(json!([
+        { "id": "a1", "identity": "zealot", "type": "ai", "created_at": "2026-02-05T10:00:00Z", "mood": "calm" },
+    ]));
+    assert!(report.is_ok());
+    assert_eq!(report.unknown.len(), 1);
+}
+
+#[test]
+fn type_changes_and_shape_are_reported() {
+    let report = AGENTS.check(json!([
+        { "id": 7, "identity": "zealot", "type": "ai", "created_at": "2026-02-05T10:00:00Z" },
+    ]));
+    assert!(report.error.is_some());
+
+    assert!(AGENTS.check(json!({ "agents": [] })).error.is_some());
+    assert!(SPAWN_EVENTS.check(json!({})).missing.contains("events"));
+}
+
+#[test]
+fn missing_required_field_fails_decoding() {
+    let decoded = serde_json::from_value::<Vec<AgentPayload>>(json!([
+        { "identity": "zealot", "type": "ai", "created_at": "2026-02-05T10:00:00Z" },
+    ]));
+    assert!(
+        decoded
+            .unwrap_err()
+            .to_string()
+            .contains("missing field `id`")
+    );
+
+    let payload: AgentPayload = serde_json::from_value(json!({
+        "id": "a1", "identity": "zealot", "type": "human", "created_at": "2026-02-05T10:00:00Z",
+    }))
+    .expect("decode");
+    let agent = Agent::from(payload);
+    assert_eq!(agent.agent_type, "human");
+    assert_eq!(agent.model, None);
+}
+
+#[test]
+fn bad_rows_are_dropped_individually() {
+    let (rows, rejected) = wire::decode_rows::<AgentPayload>(vec![
+        json!({ "id": "a1", "identity": "zealot", "type": "ai", "created_at": "2026-02-05T10:00:00Z" }),
+        json!({ "id": "a2", "identity": null, "type": "ai", "created_at": "2026-02-05T10:00:00Z" }),
+    ]);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].id, "a1");
+    assert_eq!(rejected.len(), 1);
+    assert!(rejected[0].starts_with("row 1:"));
+}
+
+#[tokio::test]
+async fn malformed_rows_are_skipped_and_reported_in_endpoint_stats() {
+    let url = serve_json(json!([
+        { "id": "a1", "identity": "zealot", "type": "ai", "created_at": "2026-02-05T10:00:00Z" },
+        { "id": "a2", "identity": "sentinel", "type": "ai" },
+    ]))
+    .await;
+    let src = Source::new(ApiClient::new(url).expect("client"));
+
+    let agents = src.get_agents().await.expect("agents");
+    assert_eq!(agents.len(), 1);
+    let stats = src.endpoint_stats();
+    let agents_stats = stats
+        .iter()
+        .find(|s| s.endpoint == Endpoint::Agents)
+        .expect("stats");
+    assert!(!agents_stats.is_failing());
+    assert_eq!(agents_stats.rejected.len(), 1);
+    assert!(agents_stats.rejected[0].contains("created_at"));
+}
+
+#[tokio::test]
+async fn ledger_rows_outside_the_query_are_dropped_client_side() {
+    // A server that ignores `agent_id`, `type` and the cursors.
+    let url = serve_json(json!([
+        { "id": "t1", "agent_id": "a1", "type": "task", "created_at": "2026-02-05T10:02:00Z" },
+        { "id": "t2", "agent_id": "a2", "type": "task", "created_at": "2026-02-05T10:01:00Z" },
+        { "id": "d1", "agent_id": "a1", "type": "decision", "created_at": "2026-02-05T10:01:00Z" },
+        { "id": "t3", "agent_id": "a1", "type": "task", "created_at": "2026-02-05T10:00:00Z" },
+    ]))
+    .await;
+    let client = ApiClient::new(url).expect("client");
+
+    let rows = client
+        .get_ledger(
+            &LedgerQuery::new(10)
+                .agent("a1")
+                .primitive("task")
+                .since("2026-02-05T10:00:00Z"),
+        )
+        .await
+        .expect("ledger");
+    let ids: Vec<&str> = rows.iter().map(|a| a.primitive_id.as_str()).collect();
+    assert_eq!(ids, ["t1", "t3"]);
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn mock_server_honours_the_contract() {
+    let path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/scenario.json");
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(mock::serve(
+        listener,
+        Scenario::load(&path).expect("scenario"),
+    ));
+
+    let reports =
+        space_cmd::contract::check(&ApiClient::new(format!("http://{}", addr)).expect("client"))
+            .await;
+    assert_eq!(reports.len(), 6);
+    for report in &reports {
+        assert!(report.is_ok(), "{:?}", report);
+        assert!(report.unknown.is_empty(), "{:?}", report);
+    }
+}
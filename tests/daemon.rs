@@ -0,0 +1,129 @@
+use space_cmd::app::{AppState, DaemonChange, ToastKind};
+use space_cmd::bridge::Outcome;
+use space_cmd::command::{Command, parse};
+use space_cmd::schema::DaemonStatus;
+use std::time::{Duration, Instant};
+
+fn status(running: bool, enabled: bool, concurrency: i32) -> DaemonStatus {
+    DaemonStatus {
+        running,
+        pid: None,
+        enabled,
+        concurrency,
+        active_count: 1,
+        last_skip: None,
+    }
+}
+
+#[test]
+fn swarm_toggle_is_pending_until_status_confirms() {
+    let mut state = AppState::new();
+    state.set_daemon(status(true, true, 2));
+
+    let cmd = state.swarm_toggle_command();
+    assert_eq!(cmd, Command::Swarm(false));
+    state.begin_daemon_change(&cmd);
+    assert_eq!(
+        state.daemon_pending.as_ref().map(|p| p.change),
+        Some(DaemonChange::Enabled(false))
+    );
+
+    state.set_daemon(status(true, true, 2));
+    assert!(state.daemon_pending.is_some());
+
+    state.set_daemon(status(true, false, 2));
+    assert!(state.daemon_pending.is_none());
+}
+
+#[test]
+fn concurrency_steps_from_pending_target() {
+    let mut state = AppState::new();
+    state.set_daemon(status(true, true, 2));
+
+    let up = state.concurrency_command(1).expect("command");
+    assert_eq!(up, Command::Concurrency(3));
+    state.begin_daemon_change(&up);
+    assert_eq!(state.concurrency_command(1), Some(Command::Concurrency(4)));
+}
+
+#[test]
+fn concurrency_never_drops_below_one() {
+    let mut state = AppState::new();
+    state.set_daemon(status(true, true, 1));
+    assert_eq!(state.concurrency_command(-1), None);
+}
+
+#[test]
+fn failed_change_clears_pending_and_reports_error() {
+    let mut state = AppState::new();
+    state.set_daemon(status(true, true, 2));
+    let cmd = Command::Concurrency(5);
+    state.begin_daemon_change(&cmd);
+
+    state.finish_command(
+        Some(&cmd),
+        Outcome {
+            ok: false,
+            message: "http 500: boom".to_string(),
+        },
+    );
+    assert!(state.daemon_pending.is_none());
+    assert_eq!(state.active_toast().map(|t| t.kind), Some(ToastKind::Error));
+}
+
+#[test]
+fn stopping_daemon_asks_for_confirmation() {
+    let mut state = AppState::new();
+    state.set_daemon(status(true, true, 2));
+    assert_eq!(state.request_daemon_toggle(), None);
+    assert_eq!(state.take_confirmed(), Some(Command::Daemon(false)));
+
+    state.set_daemon(status(false, true, 2));
+    assert_eq!(state.request_daemon_toggle(), Some(Command::Daemon(true)));
+    assert_eq!(parse(":daemon stop"), Ok(Command::Daemon(false)));
+}
+
+#[test]
+fn daemon_toggle_is_ignored_while_a_change_is_pending() {
+    let mut state = AppState::new();
+    state.set_daemon(status(false, true, 2));
+    let cmd = state.request_daemon_toggle().expect("start");
+    state.begin_daemon_change(&cmd);
+
+    assert_eq!(state.request_daemon_toggle(), None);
+    assert!(state.confirm.is_none());
+    assert_eq!(state.active_toast().map(|t| t.kind), Some(ToastKind::Error));
+
+    state.set_daemon(status(true, true, 2));
+    assert!(state.daemon_pending.is_none());
+    assert_eq!(state.request_daemon_toggle(), None);
+    assert_eq!(state.take_confirmed(), Some(Command::Daemon(false)));
+}
+
+#[test]
+fn unconfirmed_change_expires_without_a_new_status() {
+    let mut state = AppState::new();
+    state.set_daemon(status(false, true, 2));
+    let cmd = state.request_daemon_toggle().expect("start");
+    state.begin_daemon_change(&cmd);
+    state.finish_command(
+        Some(&cmd),
+        Outcome {
+            ok: true,
+            message: "daemon started".to_string(),
+        },
+    );
+    // Ask the poller for a fresh status instead of waiting on the WS.
+    assert_eq!(state.focus().daemon_check, 1);
+
+    state.expire_daemon_pending();
+    assert!(state.daemon_pending.is_some());
+
+    if let Some(pending) = state.daemon_pending.as_mut() {
+        pending.since = Instant::now() - Duration::from_secs(11);
+    }
+    state.expire_daemon_pending();
+    assert!(state.daemon_pending.is_none());
+    assert_eq!(state.active_toast().map(|t| t.kind), Some(ToastKind::Error));
+    assert_eq!(state.request_daemon_toggle(), Some(Command::Daemon(true)));
+}
@@ -0,0 +1,124 @@
+use space_cmd::app::{AppState, AutocompleteMode};
+
+fn typed(text: &str) -> AppState {
+    let mut state = AppState::new();
+    text.chars().for_each(|ch| state.add_char(ch));
+    state
+}
+
+#[test]
+fn typing_inserts_at_the_cursor() {
+    let mut state = typed("helo");
+    state.cursor_left();
+    state.add_char('l');
+    assert_eq!(state.input_text, "hello");
+
+    state.cursor_home();
+    state.add_char('>');
+    state.cursor_end();
+    state.add_char('!');
+    assert_eq!(state.input_text, ">hello!");
+}
+
+#[test]
+fn backspace_and_delete_work_around_the_cursor() {
+    let mut state = typed("héllo");
+    state.cursor_home();
+    state.cursor_right();
+    state.cursor_right();
+    state.backspace();
+    assert_eq!(state.input_text, "hllo");
+    state.delete_forward();
+    assert_eq!(state.input_text, "hlo");
+    assert_eq!(state.cursor(), 1);
+
+    state.cursor_end();
+    state.delete_forward();
+    assert_eq!(state.input_text, "hlo");
+}
+
+#[test]
+fn word_operations_stop_at_whitespace() {
+    let mut state = typed("send @zealot  now");
+    state.word_left();
+    assert_eq!(state.cursor(), "send @zealot  ".len());
+    state.word_left();
+    assert_eq!(state.cursor(), "send ".len());
+    state.word_right();
+    assert_eq!(state.cursor(), "send @zealot".len());
+
+    state.cursor_end();
+    state.delete_word_back();
+    assert_eq!(state.input_text, "send @zealot  ");
+    state.delete_word_back();
+    assert_eq!(state.input_text, "send ");
+
+    state.add_char('x');
+    state.cursor_left();
+    state.kill_line_back();
+    assert_eq!(state.input_text, "x");
+    assert_eq!(state.cursor(), 0);
+}
+
+#[test]
+fn newlines_make_multiline_input_with_line_movement() {
+    let mut state = typed("first");
+    state.insert_newline();
+    "second line".chars().for_each(|ch| state.add_char(ch));
+    assert_eq!(state.input_line_count(), 2);
+    assert_eq!(state.cursor_position(), (1, 11));
+
+    assert!(state.cursor_up());
+    assert_eq!(state.cursor_position(), (0, 5));
+    assert!(!state.cursor_up());
+
+    state.cursor_home();
+    assert!(state.cursor_down());
+    assert_eq!(state.cursor_position(), (1, 0));
+    state.kill_line_back();
+    assert_eq!(state.input_text, "first\nsecond line");
+    state.backspace();
+    assert_eq!(state.input_text, "firstsecond line");
+}
+
+#[test]
+fn paste_keeps_newlines_and_does_not_submit() {
+    let mut state = typed("ab");
+    state.cursor_left();
+    state.paste("one\r\ntwo");
+    assert_eq!(state.input_text, "aone\ntwob");
+    assert_eq!(state.cursor_position(), (1, 3));
+    assert!(state.input_history.is_empty());
+}
+
+#[test]
+fn history_and_submit_put_the_cursor_at_the_end() {
+    let mut state = typed("older");
+    state.submit_input();
+    state.add_char('o');
+    state.cursor_home();
+    state.history_prev();
+    assert_eq!(state.cursor(), "older".len());
+
+    state.add_char('!');
+    assert_eq!(state.input_text, "older!");
+    assert_eq!(state.submit_input(), Some("older!".to_string()));
+    assert_eq!(state.cursor(), 0);
+}
+
+#[test]
+fn autocomplete_completes_the_word_under_the_cursor() {
+    let mut state = typed("hi  there");
+    state.word_left();
+    state.cursor_left();
+    state.add_char('@');
+    state.add_char('z');
+    state.detect_and_trigger_autocomplete();
+    assert_eq!(state.autocomplete_mode, Some(AutocompleteMode::Agent));
+    assert_eq!(state.autocomplete_query, "z");
+
+    state.autocomplete_list = vec!["zealot".to_string()];
+    state.autocomplete_select();
+    assert_eq!(state.input_text, "hi @zealot  there");
+    assert_eq!(state.cursor(), "hi @zealot ".len());
+}
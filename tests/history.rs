@@ -0,0 +1,124 @@
+use space_cmd::app::{AppState, mention};
+use space_cmd::history;
+
+fn with_history(entries: &[&str]) -> AppState {
+    let mut state = AppState::new();
+    state.input_history = entries.iter().map(|e| e.to_string()).collect();
+    state
+}
+
+fn type_text(state: &mut AppState, text: &str) {
+    text.chars().for_each(|ch| state.add_char(ch));
+}
+
+#[test]
+fn submitting_dedupes_and_caps_history() {
+    let mut state = AppState::new();
+    state.limits.history = 3;
+    for line in ["a", "b", "a", "c", "d"] {
+        type_text(&mut state, line);
+        state.submit_input();
+    }
+    assert_eq!(state.input_history, ["d", "c", "a"]);
+}
+
+#[test]
+fn history_file_round_trips_and_merges_sessions() {
+    let path = std::env::temp_dir()
+        .join(format!("space-cmd-history-{}", std::process::id()))
+        .join("history.json");
+
+    let mut first = AppState::new();
+    first.open_history(Some(path.clone()));
+    type_text(&mut first, "@zealot rebase\nthen push");
+    first.submit_input();
+
+    let mut second = AppState::new();
+    second.open_history(Some(path.clone()));
+    assert_eq!(second.input_history, ["@zealot rebase\nthen push"]);
+    type_text(&mut second, "status");
+    second.submit_input();
+    type_text(&mut first, "@zealot rebase\nthen push");
+    first.submit_input();
+
+    let saved = history::load(&path, 10);
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    assert_eq!(saved, ["@zealot rebase\nthen push", "status"]);
+}
+
+#[test]
+fn up_and_down_only_visit_entries_with_the_typed_prefix() {
+    let mut state = with_history(&["deploy web", "status", "deploy api", "deploy"]);
+    type_text(&mut state, "deploy");
+
+    state.history_prev();
+    assert_eq!(state.input_text, "deploy web");
+    state.history_prev();
+    assert_eq!(state.input_text, "deploy api");
+    state.history_prev();
+    assert_eq!(state.input_text, "deploy api");
+
+    state.history_next();
+    assert_eq!(state.input_text, "deploy web");
+    state.history_next();
+    assert_eq!(state.input_text, "deploy");
+    assert_eq!(state.history_idx, None);
+}
+
+#[test]
+fn agent_mention_scopes_history_to_that_agent() {
+    assert_eq!(mention("@zealot fix ci"), Some("@zealot"));
+    assert_eq!(mention("fix @zealot"), None);
+    assert_eq!(mention("@ alone"), None);
+
+    let mut state = with_history(&["@sentinel check ci", "@zealot rebase", "@zealotry x"]);
+    type_text(&mut state, "@zealot ");
+    state.history_prev();
+    assert_eq!(state.input_text, "@zealot rebase");
+    state.history_prev();
+    assert_eq!(state.input_text, "@zealot rebase");
+
+    state.input_text = "@sentinel ".to_string();
+    state.start_history_search();
+    state.search_push('c');
+    assert_eq!(state.input_text, "@sentinel check ci");
+}
+
+#[test]
+fn reverse_search_narrows_walks_older_and_accepts() {
+    let mut state = with_history(&["git push", "cargo test", "git pull", "ls"]);
+    type_text(&mut state, "draft");
+    state.start_history_search();
+    assert_eq!(state.input_text, "git push");
+
+    state.search_push('G');
+    state.search_push('i');
+    assert_eq!(state.input_text, "git push");
+    state.search_older();
+    assert_eq!(state.input_text, "git pull");
+    state.search_older();
+    assert_eq!(state.input_text, "git pull");
+    assert!(state.search_match().is_some());
+
+    state.search_push('x');
+    assert!(state.search_match().is_none());
+    assert_eq!(state.input_text, "git pull");
+    state.search_backspace();
+    state.accept_history_search();
+    assert!(state.history_search.is_none());
+    assert_eq!(state.input_text, "git push");
+    assert_eq!(state.cursor(), "git push".len());
+}
+
+#[test]
+fn cancelling_search_restores_the_line() {
+    let mut state = with_history(&["git push"]);
+    type_text(&mut state, "draft");
+    state.start_history_search();
+    state.search_push('p');
+    assert_eq!(state.input_text, "git push");
+
+    state.cancel_history_search();
+    assert_eq!(state.input_text, "draft");
+    assert!(state.history_search.is_none());
+}
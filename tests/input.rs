@@ -1,4 +1,4 @@
-use space_cmd::app::AppState;
+use space_cmd::app::{AppState, ToastKind};
 
 #[test]
 fn characters_accumulate_in_input() {
@@ -85,3 +85,18 @@ fn empty_submit_returns_none() {
     assert_eq!(result, None);
     assert!(state.input_history.is_empty());
 }
+
+#[test]
+fn pending_toast_stays_until_replaced() {
+    let mut state = AppState::new();
+    state.show_toast(ToastKind::Pending, "sending");
+    assert_eq!(
+        state.active_toast().map(|t| t.kind),
+        Some(ToastKind::Pending)
+    );
+
+    state.show_toast(ToastKind::Error, "bridge: no such channel");
+    let toast = state.active_toast().expect("toast");
+    assert_eq!(toast.kind, ToastKind::Error);
+    assert_eq!(toast.message, "bridge: no such channel");
+}
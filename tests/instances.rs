@@ -0,0 +1,154 @@
+use space_cmd::app::{AppState, SidebarTab};
+use space_cmd::command::Command;
+use space_cmd::config::Config;
+use space_cmd::record::Record;
+use space_cmd::schema::{Agent, Spawn};
+
+fn agent(id: &str, identity: &str) -> Agent {
+    Agent {
+        id: id.to_string(),
+        identity: identity.to_string(),
+        agent_type: "ai".to_string(),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+fn spawn(id: &str, agent_id: &str, created_at: &str) -> Spawn {
+    Spawn {
+        id: id.to_string(),
+        agent_id: agent_id.to_string(),
+        status: "active".to_string(),
+        created_at: created_at.to_string(),
+        ..Default::default()
+    }
+}
+
+fn two_instances() -> AppState {
+    let mut state = AppState::new();
+    state.set_instances(vec!["local".to_string(), "remote".to_string()]);
+    state
+}
+
+fn agents(list: Vec<Agent>) -> Record {
+    Record::Agents { agents: list }
+}
+
+#[test]
+fn config_lists_named_instances() {
+    let config = Config::parse(
+        r#"
+[[instances]]
+name = "local"
+url = "http://localhost:8228"
+
+[[instances]]
+name = "remote"
+url = "http://box:8228"
+"#,
+    )
+    .expect("parse");
+    let names: Vec<&str> = config.instances.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, ["local", "remote"]);
+    assert_eq!(config.instances()[1].url, "http://box:8228");
+}
+
+#[test]
+fn config_rejects_duplicate_names() {
+    let raw = r#"
+[[instances]]
+name = "local"
+url = "http://a"
+
+[[instances]]
+name = "local"
+url = "http://b"
+"#;
+    assert!(Config::parse(raw).is_err());
+}
+
+#[test]
+fn empty_config_falls_back_to_one_instance() {
+    let instances = Config::parse("").expect("parse").instances();
+    assert_eq!(instances.len(), 1);
+    assert!(instances[0].is_default());
+}
+
+#[test]
+fn single_view_ignores_other_instances() {
+    let mut state = two_instances();
+    assert!(state.apply_instance_record(0, &agents(vec![agent("a1", "zealot")])));
+    assert!(!state.apply_instance_record(1, &agents(vec![agent("b1", "sentinel")])));
+    assert_eq!(state.agents.len(), 1);
+    assert_eq!(state.instance_tag("a1"), None);
+}
+
+#[test]
+fn merged_view_tags_rows_and_replaces_per_instance() {
+    let mut state = two_instances();
+    state.toggle_merged();
+    state.apply_instance_record(0, &agents(vec![agent("a1", "zealot")]));
+    state.apply_instance_record(1, &agents(vec![agent("b1", "sentinel")]));
+    state.apply_instance_record(0, &agents(vec![agent("a2", "harbinger")]));
+
+    let ids: Vec<&str> = state.agents.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["a2", "b1"]);
+    assert_eq!(state.instance_tag("b1"), Some("remote"));
+    assert_eq!(state.resolve_identity("b1"), "sentinel");
+
+    state.apply_instance_record(
+        1,
+        &Record::Spawns {
+            spawns: vec![spawn("s-b", "b1", "2026-02-05T10:05:00Z")],
+        },
+    );
+    state.apply_instance_record(
+        0,
+        &Record::Spawns {
+            spawns: vec![spawn("s-a", "a2", "2026-02-05T10:01:00Z")],
+        },
+    );
+    // Newest first across instances.
+    assert_eq!(state.spawns[0].id, "s-b");
+}
+
+#[test]
+fn merged_panes_follow_the_focused_agents_instance() {
+    let mut state = two_instances();
+    state.toggle_merged();
+    state.apply_instance_record(0, &agents(vec![agent("a1", "zealot")]));
+    state.apply_instance_record(1, &agents(vec![agent("b1", "sentinel")]));
+    state.active_tab = SidebarTab::Agents;
+    state.active_agent_idx = 1;
+
+    assert_eq!(state.shown_instance(), 1);
+    assert!(state.focus_for(1).activity.is_some());
+    assert!(state.focus_for(0).activity.is_none());
+}
+
+#[test]
+fn commands_route_to_the_spawns_instance() {
+    let mut state = two_instances();
+    state.toggle_merged();
+    state.apply_instance_record(
+        1,
+        &Record::Spawns {
+            spawns: vec![spawn("remote-spawn", "b1", "2026-02-05T10:00:00Z")],
+        },
+    );
+    assert_eq!(
+        state.command_instance(&Command::Kill("remote".to_string())),
+        1
+    );
+    assert_eq!(state.command_instance(&Command::Swarm(true)), 0);
+}
+
+#[test]
+fn switching_instance_resets_the_view() {
+    let mut state = two_instances();
+    state.apply_instance_record(0, &agents(vec![agent("a1", "zealot")]));
+    state.next_instance();
+    assert_eq!(state.instance, 1);
+    assert!(state.agents.is_empty());
+    assert!(state.apply_instance_record(1, &agents(vec![agent("b1", "sentinel")])));
+}
@@ -1,3 +1,23 @@
+mod actions;
 mod app_state;
+mod auth;
 mod autocomplete;
+mod cache;
+mod command;
+mod config;
+mod contract;
+mod daemon;
+mod editor;
+mod history;
 mod input;
+mod instances;
+mod keymap;
+mod live;
+mod merge;
+mod mock;
+mod poll;
+mod record;
+mod search;
+mod source;
+mod transcript;
+mod tree;
@@ -0,0 +1,183 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use space_cmd::app::{AppState, InputMode};
+use space_cmd::config::Config;
+use space_cmd::keymap::{Action, Key, KeySpec, Keymap};
+use std::collections::BTreeMap;
+
+fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+fn char_key(ch: char) -> KeyEvent {
+    press(KeyCode::Char(ch), KeyModifiers::NONE)
+}
+
+#[test]
+fn default_keymap_matches_documented_bindings() {
+    let keymap = Keymap::default();
+    assert_eq!(keymap.action(&char_key('q')), Some(Action::Quit));
+    assert_eq!(keymap.action(&char_key('j')), Some(Action::Down));
+    assert_eq!(
+        keymap.action(&press(KeyCode::Char('J'), KeyModifiers::SHIFT)),
+        Some(Action::ScrollPaneDown)
+    );
+    assert_eq!(
+        keymap.action(&press(KeyCode::Char('j'), KeyModifiers::CONTROL)),
+        Some(Action::NextSpawn)
+    );
+    assert_eq!(
+        keymap.action(&press(KeyCode::Char('?'), KeyModifiers::SHIFT)),
+        Some(Action::Help)
+    );
+    assert_eq!(keymap.action(&char_key('w')), None);
+}
+
+#[test]
+fn keys_parse_and_print_back() {
+    for spec in [
+        "j", "J", "ctrl+j", "alt+x", "space", "pgdn", "f5", "+", "ctrl++",
+    ] {
+        let key = Key::parse(spec).expect(spec);
+        assert_eq!(key.to_string(), spec);
+    }
+    assert_eq!(
+        Key::parse("Ctrl+K").expect("parse").modifiers,
+        KeyModifiers::CONTROL
+    );
+    assert!(Key::parse("hyper+x").is_err());
+    assert!(Key::parse("escape-hatch").is_err());
+}
+
+#[test]
+fn overrides_replace_an_actions_defaults() {
+    let mut overrides = BTreeMap::new();
+    overrides.insert(Action::Kill, KeySpec::One("X".to_string()));
+    let keymap = Keymap::new(&overrides).expect("keymap");
+
+    assert_eq!(keymap.action(&char_key('x')), None);
+    assert_eq!(keymap.action(&char_key('X')), Some(Action::Kill));
+    assert_eq!(keymap.keys(Action::Kill)[0].to_string(), "X");
+}
+
+#[test]
+fn conflicting_bindings_are_rejected() {
+    let mut overrides = BTreeMap::new();
+    overrides.insert(Action::Kill, KeySpec::One("d".to_string()));
+    let err = Keymap::new(&overrides).expect_err("conflict");
+    assert!(
+        err.contains("cycle-pane") && err.contains("kill"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn config_keys_section_feeds_the_keymap() {
+    let config = Config::parse(
+        r#"
+[keys]
+pause = ["p", "space"]
+quit = "ctrl+q"
+"#,
+    )
+    .expect("parse");
+    let keymap = config.keymap();
+    assert_eq!(keymap.action(&char_key('p')), Some(Action::Pause));
+    assert_eq!(keymap.action(&char_key('q')), None);
+
+    assert!(Config::parse("[keys]\nteleport = \"T\"\n").is_err());
+}
+
+#[test]
+fn insert_mode_is_entered_and_left_explicitly() {
+    let mut state = AppState::new();
+    assert_eq!(state.input_mode, InputMode::Normal);
+
+    state.start_input(Some(':'));
+    assert!(state.is_inserting());
+    assert_eq!(state.input_text, ":");
+
+    state.input_text = "half a thought".to_string();
+    state.leave_insert();
+    assert_eq!(state.input_mode, InputMode::Normal);
+    assert_eq!(state.input_text, "half a thought");
+
+    // `:` on a non-empty line resumes typing instead of starting a command.
+    state.start_input(Some(':'));
+    assert_eq!(state.input_text, "half a thought");
+
+    state.submit_input();
+    assert_eq!(state.input_mode, InputMode::Normal);
+}
+
+// Exhaustive, so a new variant fails to build here until it is placed in `Action::ALL`.
+fn position(action: Action) -> usize {
+    match action {
+        Action::Quit => 0,
+        Action::Help => 1,
+        Action::Insert => 2,
+        Action::Command => 3,
+        Action::Mention => 4,
+        Action::File => 5,
+        Action::ClearInput => 6,
+        Action::HistorySearch => 7,
+        Action::Search => 8,
+        Action::SearchNext => 9,
+        Action::SearchPrev => 10,
+        Action::SwitchTab => 11,
+        Action::Down => 12,
+        Action::Up => 13,
+        Action::NextSpawn => 14,
+        Action::PrevSpawn => 15,
+        Action::ScrollPaneDown => 16,
+        Action::ScrollPaneUp => 17,
+        Action::ScrollActivityDown => 18,
+        Action::ScrollActivityUp => 19,
+        Action::Transcript => 20,
+        Action::Pause => 21,
+        Action::AllStream => 22,
+        Action::CyclePane => 23,
+        Action::Expand => 24,
+        Action::Tree => 25,
+        Action::Collapse => 26,
+        Action::Kill => 27,
+        Action::Retry => 28,
+        Action::FollowUp => 29,
+        Action::Swarm => 30,
+        Action::ConcurrencyUp => 31,
+        Action::ConcurrencyDown => 32,
+        Action::Daemon => 33,
+        Action::NextInstance => 34,
+        Action::Merged => 35,
+    }
+}
+
+#[test]
+fn every_action_is_listed_named_like_its_config_key_and_bound() {
+    let keymap = Keymap::default();
+    for (i, action) in Action::ALL.into_iter().enumerate() {
+        assert_eq!(
+            position(action),
+            i,
+            "{:?} out of place in Action::ALL",
+            action
+        );
+        assert_eq!(
+            serde_json::to_value(action).expect("serialize"),
+            action.name(),
+            "{:?}",
+            action
+        );
+        let parsed: Action = serde_json::from_value(action.name().into()).expect("deserialize");
+        assert_eq!(parsed, action);
+        assert!(
+            !keymap.keys(action).is_empty(),
+            "{:?} has no default key",
+            action
+        );
+    }
+    assert_eq!(
+        keymap.action(&press(KeyCode::Char('F'), KeyModifiers::SHIFT)),
+        Some(Action::File)
+    );
+}
@@ -0,0 +1,142 @@
+use space_cmd::app::AppState;
+use space_cmd::live::LiveEvent;
+use space_cmd::schema::{Activity, Agent, Spawn, TailEntry};
+
+fn agent(id: &str, identity: &str) -> Agent {
+    Agent {
+        id: id.to_string(),
+        identity: identity.to_string(),
+        agent_type: "ai".to_string(),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+fn spawn(id: &str, status: &str) -> Spawn {
+    Spawn {
+        id: id.to_string(),
+        agent_id: "a1".to_string(),
+        status: status.to_string(),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+fn tail(agent: &str) -> TailEntry {
+    TailEntry {
+        spawn: "s1".to_string(),
+        agent: agent.to_string(),
+        entry_type: "text".to_string(),
+        content: Some("hello".to_string()),
+        name: None,
+        args: None,
+        ctx_pct: None,
+    }
+}
+
+#[test]
+fn agent_event_upserts_and_registers_identity() {
+    let mut state = AppState::new();
+    state.apply_live_event(LiveEvent::Agent(agent("a1", "zealot")));
+    state.apply_live_event(LiveEvent::Agent(agent("a1", "zealot2")));
+
+    assert_eq!(state.agents.len(), 1);
+    assert_eq!(state.agents[0].identity, "zealot2");
+    assert_eq!(state.resolve_identity("a1"), "zealot2");
+}
+
+#[test]
+fn spawn_event_updates_existing_in_place() {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("s1", "active")];
+    state.apply_live_event(LiveEvent::Spawn(spawn("s1", "done")));
+
+    assert_eq!(state.spawns.len(), 1);
+    assert_eq!(state.spawns[0].status, "done");
+}
+
+#[test]
+fn new_spawn_keeps_focus_on_same_spawn() {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("s1", "active"), spawn("s2", "active")];
+    state.active_spawn_idx = 1;
+    state.selected_spawn_idx = Some(0);
+
+    state.apply_live_event(LiveEvent::Spawn(spawn("s3", "active")));
+
+    assert_eq!(state.spawns[0].id, "s3");
+    assert_eq!(state.spawns[state.active_spawn_idx].id, "s2");
+    assert_eq!(state.selected_spawn().map(|s| s.id.as_str()), Some("s1"));
+}
+
+#[test]
+fn ledger_event_feeds_active_agent_activity() {
+    let mut state = AppState::new();
+    state.agents = vec![agent("a1", "zealot")];
+    let act = Activity {
+        id: None,
+        agent_id: "a2".to_string(),
+        spawn_id: None,
+        primitive: "task".to_string(),
+        primitive_id: "t1".to_string(),
+        action: "created".to_string(),
+        field: None,
+        after: None,
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+    };
+
+    state.apply_live_event(LiveEvent::Ledger(act.clone()));
+    assert_eq!(state.ledger.len(), 1);
+    assert!(state.activity.is_empty());
+
+    state.apply_live_event(LiveEvent::Ledger(Activity {
+        agent_id: "a1".to_string(),
+        ..act
+    }));
+    assert_eq!(state.ledger.len(), 2);
+    assert_eq!(state.activity.len(), 1);
+}
+
+#[test]
+fn ledger_event_after_agent_switch_replaces_previous_agents_rows() {
+    let mut state = AppState::new();
+    state.agents = vec![agent("a1", "zealot"), agent("a2", "sentinel")];
+    let act = Activity {
+        id: None,
+        agent_id: "a1".to_string(),
+        spawn_id: None,
+        primitive: "task".to_string(),
+        primitive_id: "t1".to_string(),
+        action: "created".to_string(),
+        field: None,
+        after: None,
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+    };
+    state.set_activity(Some("a1".to_string()), vec![act.clone()]);
+
+    state.active_agent_idx = 1;
+    state.apply_live_event(LiveEvent::Ledger(Activity {
+        agent_id: "a2".to_string(),
+        primitive_id: "t2".to_string(),
+        ..act
+    }));
+    assert_eq!(state.activity_scope.as_deref(), Some("a2"));
+    assert_eq!(state.activity.len(), 1);
+    assert_eq!(state.activity[0].agent_id, "a2");
+}
+
+#[test]
+fn tail_event_filters_by_active_agent() {
+    let mut state = AppState::new();
+    state.agents = vec![agent("a1", "zealot")];
+
+    state.apply_live_event(LiveEvent::Tail(tail("sentinel")));
+    assert!(state.stream.is_empty());
+
+    state.apply_live_event(LiveEvent::Tail(tail("zealot")));
+    assert_eq!(state.stream.len(), 1);
+
+    state.all_stream = true;
+    state.apply_live_event(LiveEvent::Tail(tail("sentinel")));
+    assert_eq!(state.stream.len(), 2);
+}
@@ -0,0 +1,143 @@
+use space_cmd::app::AppState;
+use space_cmd::schema::{Activity, Spawn, TailEntry};
+
+fn act(id: &str, created_at: &str) -> Activity {
+    Activity {
+        id: None,
+        agent_id: "a1".to_string(),
+        spawn_id: None,
+        primitive: "decision".to_string(),
+        primitive_id: id.to_string(),
+        action: "created".to_string(),
+        field: None,
+        after: None,
+        created_at: created_at.to_string(),
+    }
+}
+
+fn spawn(id: &str) -> Spawn {
+    Spawn {
+        id: id.to_string(),
+        agent_id: "a1".to_string(),
+        status: "running".to_string(),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+fn tail(content: &str) -> TailEntry {
+    TailEntry {
+        spawn: "s1".to_string(),
+        agent: "zealot".to_string(),
+        entry_type: "text".to_string(),
+        content: Some(content.to_string()),
+        name: None,
+        args: None,
+        ctx_pct: None,
+    }
+}
+
+fn ids(items: &[Activity]) -> Vec<&str> {
+    items.iter().map(|a| a.primitive_id.as_str()).collect()
+}
+
+#[test]
+fn ledger_delta_query_starts_after_newest() {
+    let mut state = AppState::new();
+    assert_eq!(state.ledger_delta_query(500).since, None);
+
+    state.ledger = vec![
+        act("l2", "2026-02-05T10:02:00Z"),
+        act("l1", "2026-02-05T10:01:00Z"),
+    ];
+    let query = state.ledger_delta_query(500);
+    assert_eq!(query.since.as_deref(), Some("2026-02-05T10:02:00Z"));
+    assert_eq!(query.limit, 500);
+}
+
+#[test]
+fn ledger_delta_prepends_unseen_and_anchors_scroll() {
+    let mut state = AppState::new();
+    state.ledger = vec![
+        act("l2", "2026-02-05T10:02:00Z"),
+        act("l1", "2026-02-05T10:01:00Z"),
+    ];
+    state.ledger_scroll_offset = 1;
+
+    let fresh = vec![
+        act("l3", "2026-02-05T10:03:00Z"),
+        act("l2", "2026-02-05T10:02:00Z"),
+    ];
+    assert!(state.merge_ledger(true, fresh));
+    assert_eq!(ids(&state.ledger), ["l3", "l2", "l1"]);
+    // Still looking at l1.
+    assert_eq!(state.ledger_scroll_offset, 2);
+
+    assert!(!state.merge_ledger(true, vec![act("l3", "2026-02-05T10:03:00Z")]));
+    assert!(!state.merge_ledger(true, Vec::new()));
+}
+
+#[test]
+fn ledger_delta_at_top_stays_at_top() {
+    let mut state = AppState::new();
+    state.ledger = vec![act("l1", "2026-02-05T10:01:00Z")];
+    state.merge_ledger(true, vec![act("l2", "2026-02-05T10:02:00Z")]);
+    assert_eq!(state.ledger_scroll_offset, 0);
+}
+
+#[test]
+fn full_ledger_refresh_reports_changes_only() {
+    let mut state = AppState::new();
+    let window = vec![act("l1", "2026-02-05T10:01:00Z")];
+    assert!(state.merge_ledger(false, window.clone()));
+    assert!(!state.merge_ledger(false, window));
+}
+
+#[test]
+fn ledger_is_bounded() {
+    let mut state = AppState::new();
+    state.ledger = (0..500)
+        .map(|i| act(&format!("old{i}"), "2026-02-05T10:00:00Z"))
+        .collect();
+    state.merge_ledger(true, vec![act("new", "2026-02-05T10:05:00Z")]);
+    assert_eq!(state.ledger.len(), 500);
+    assert_eq!(state.ledger[0].primitive_id, "new");
+    assert_eq!(state.ledger[499].primitive_id, "old498");
+}
+
+#[test]
+fn activity_delta_ignored_after_scope_change() {
+    let mut state = AppState::new();
+    state.merge_activity(None, false, vec![act("n1", "2026-02-05T10:01:00Z")]);
+    assert!(!state.merge_activity(
+        Some("a1".to_string()),
+        true,
+        vec![act("x", "2026-02-05T10:02:00Z")]
+    ));
+    assert_eq!(ids(&state.activity), ["n1"]);
+
+    assert!(state.merge_activity(None, true, vec![act("n2", "2026-02-05T10:02:00Z")]));
+    assert_eq!(ids(&state.activity), ["n2", "n1"]);
+}
+
+#[test]
+fn stream_window_shift_keeps_view_in_place() {
+    let mut state = AppState::new();
+    state.stream = vec![tail("a"), tail("b"), tail("c")];
+    state.stream_scroll_offset = 2;
+
+    assert!(state.replace_stream(vec![tail("b"), tail("c"), tail("d")]));
+    assert_eq!(state.stream_scroll_offset, 1);
+    assert!(!state.replace_stream(vec![tail("b"), tail("c"), tail("d")]));
+}
+
+#[test]
+fn spawn_selection_follows_id_across_refresh() {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("s1"), spawn("s2")];
+    state.selected_spawn_idx = Some(1);
+
+    assert!(state.replace_spawns(vec![spawn("s0"), spawn("s1"), spawn("s2")]));
+    assert_eq!(state.selected_spawn().map(|s| s.id.as_str()), Some("s2"));
+    assert!(!state.replace_spawns(vec![spawn("s0"), spawn("s1"), spawn("s2")]));
+}
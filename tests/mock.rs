@@ -0,0 +1,193 @@
+#![cfg(feature = "mock")]
+
+use space_cmd::api::{ApiClient, ApiError};
+use space_cmd::health::{RepoHealthOptions, calculate_health};
+use space_cmd::live::{self, LiveEvent, LiveUpdate};
+use space_cmd::mock::{self, Failure, Scenario};
+use space_cmd::schema::LedgerQuery;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+async fn start(scenario: Scenario) -> ApiClient {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(mock::serve(listener, scenario));
+    ApiClient::new(format!("http://{}", addr)).expect("client")
+}
+
+fn scenario() -> Scenario {
+    let path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/scenario.json");
+    Scenario::load(&path).expect("load scenario")
+}
+
+#[tokio::test]
+async fn serves_agents_and_running_lifecycle() {
+    let client = start(scenario()).await;
+
+    let agents = client.get_agents().await.expect("agents");
+    assert_eq!(agents.len(), 2);
+    assert_eq!(agents[1].agent_type, "human");
+
+    // The second lifecycle starts an hour in, so only the first is visible.
+    let spawns = client.get_spawns().await.expect("spawns");
+    assert_eq!(spawns.len(), 1);
+    assert_eq!(spawns[0].status, "active");
+
+    let events = client
+        .get_spawn_activity("s1s1s1s1-0000", 200)
+        .await
+        .expect("events");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].after.as_deref(), Some("reading the backlog"));
+
+    let tail = client.get_agent_tail("zealot", 10).await.expect("tail");
+    assert_eq!(tail.len(), 1);
+
+    let daemon = client.get_daemon_status().await.expect("daemon");
+    assert_eq!(daemon.active_count, 1);
+}
+
+#[tokio::test]
+async fn scripted_failures_hit_every_nth_request() {
+    let client = start(scenario()).await;
+
+    assert!(client.get_ledger(&LedgerQuery::new(10)).await.is_ok());
+    assert!(matches!(
+        client.get_ledger(&LedgerQuery::new(10)).await,
+        Err(ApiError::Http(503, _))
+    ));
+    assert!(client.get_ledger(&LedgerQuery::new(10)).await.is_ok());
+}
+
+#[tokio::test]
+async fn writes_update_server_state() {
+    let client = start(scenario()).await;
+
+    client.set_swarm_concurrency(5).await.expect("concurrency");
+    client.set_daemon_running(false).await.expect("stop");
+    let daemon = client.get_daemon_status().await.expect("daemon");
+    assert_eq!(daemon.concurrency, 5);
+    assert!(!daemon.running);
+
+    client.kill_spawn("s1s1s1s1-0000").await.expect("kill");
+    let spawns = client.get_spawns().await.expect("spawns");
+    assert_eq!(spawns[0].status, "done");
+    assert_eq!(spawns[0].error.as_deref(), Some("killed"));
+    assert!(client.kill_spawn("s1s1s1s1-0000").await.is_err());
+
+    client
+        .create_task("write the mock server", "h0000000-0000")
+        .await
+        .expect("task");
+    let ledger = client
+        .get_ledger(&LedgerQuery::new(10))
+        .await
+        .expect("ledger");
+    assert_eq!(ledger[0].primitive, "task");
+    assert_eq!(ledger[0].agent_id, "h0000000-0000");
+}
+
+#[tokio::test]
+async fn health_runs_against_mock_server() {
+    let client = start(Scenario::default()).await;
+    let options = RepoHealthOptions {
+        repos: vec![std::env::temp_dir()],
+        ..RepoHealthOptions::default()
+    };
+
+    let result = calculate_health(&client, options).await;
+    assert!(result.api_ok);
+    assert!(result.api_latency_ms.is_some());
+}
+
+#[tokio::test]
+async fn ledger_filters_by_agent_type_and_cursor() {
+    let client = start(Scenario::default()).await;
+    for content in ["first", "second", "third"] {
+        client
+            .create_task(content, "h0000000-0000")
+            .await
+            .expect("task");
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+
+    let all = client
+        .get_ledger(
+            &LedgerQuery::new(10)
+                .agent("h0000000-0000")
+                .primitive("task"),
+        )
+        .await
+        .expect("ledger");
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].after.as_deref(), Some("third"));
+
+    // Cursors are inclusive: the boundary row comes back too.
+    let older = client
+        .get_ledger(&LedgerQuery::new(10).before(all[1].created_at.clone()))
+        .await
+        .expect("older");
+    assert_eq!(older.len(), 2);
+    assert_eq!(older[0].after.as_deref(), Some("second"));
+
+    let newer = client
+        .get_ledger(&LedgerQuery::new(10).since(all[1].created_at.clone()))
+        .await
+        .expect("newer");
+    assert_eq!(newer.len(), 2);
+
+    let nobody = client
+        .get_ledger(&LedgerQuery::new(10).agent("someone-else"))
+        .await
+        .expect("filtered");
+    assert!(nobody.is_empty());
+}
+
+#[tokio::test]
+async fn websocket_pushes_changes_after_connect() {
+    let client = start(scenario()).await;
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    tokio::spawn(live::run(client.clone(), tx));
+    let next = async |rx: &mut mpsc::UnboundedReceiver<LiveUpdate>| {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("live update")
+            .expect("channel open")
+    };
+    assert!(matches!(next(&mut rx).await, LiveUpdate::Connected));
+
+    client
+        .create_task("ship it", "h0000000-0000")
+        .await
+        .expect("task");
+    // Lifecycle progress may be pushed first; the new task must follow.
+    loop {
+        if let LiveUpdate::Event(event) = next(&mut rx).await
+            && let LiveEvent::Ledger(act) = *event
+        {
+            assert_eq!(act.after.as_deref(), Some("ship it"));
+            break;
+        }
+    }
+}
+
+#[tokio::test]
+async fn server_errors_are_http_errors_not_decode_errors() {
+    let client = start(Scenario {
+        failures: vec![Failure {
+            path: "/api/agents".to_string(),
+            status: 500,
+            every: 1,
+            from_s: 0,
+            until_s: None,
+        }],
+        ..Scenario::default()
+    })
+    .await;
+
+    let err = client.get_agents().await.expect_err("500");
+    assert!(matches!(&err, ApiError::Http(500, body) if body == "scripted failure"));
+    assert!(client.get_spawns().await.is_ok());
+}
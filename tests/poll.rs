@@ -0,0 +1,150 @@
+#[cfg(feature = "mock")]
+use space_cmd::api::ApiClient;
+#[cfg(feature = "mock")]
+use space_cmd::mock::{self, Scenario};
+use space_cmd::poll::{self, Focus, Settings};
+use space_cmd::record::Record;
+use space_cmd::schema::LedgerQuery;
+use space_cmd::source::{FixtureSource, Source};
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(feature = "mock")]
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, watch};
+
+fn fixture() -> Source<FixtureSource> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/swarm.json");
+    Source::new(FixtureSource::load(&path).expect("fixture"))
+}
+
+async fn drain(rx: &mut mpsc::UnboundedReceiver<Record>, window: Duration) -> Vec<Record> {
+    let mut records = Vec::new();
+    let deadline = tokio::time::Instant::now() + window;
+    while let Ok(Some(record)) = tokio::time::timeout_at(deadline, rx.recv()).await {
+        records.push(record);
+    }
+    records
+}
+
+#[tokio::test]
+async fn fetches_everything_in_focus_on_resync() {
+    let (_focus_tx, focus_rx) = watch::channel(Focus {
+        resync: 1,
+        activity: Some(LedgerQuery::new(500)),
+        tail: Some(Some("zealot".to_string())),
+        spawn_id: Some("s1s1s1s1-0000".to_string()),
+        ..Focus::default()
+    });
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    poll::spawn(Arc::new(fixture()), focus_rx, tx, Settings::default());
+
+    let records = drain(&mut rx, Duration::from_millis(200)).await;
+    let has = |pred: fn(&Record) -> bool| records.iter().any(pred);
+    assert!(has(|r| matches!(r, Record::Agents { .. })));
+    assert!(has(|r| matches!(r, Record::Spawns { .. })));
+    assert!(has(|r| matches!(r, Record::Activity { delta: false, .. })));
+    assert!(has(|r| matches!(r, Record::Tail { agent: Some(_), .. })));
+    assert!(has(|r| matches!(r, Record::SpawnEvents { .. })));
+    assert!(has(|r| matches!(r, Record::Daemon { .. })));
+    // The ledger pane is hidden.
+    assert!(!has(|r| matches!(r, Record::Ledger { .. })));
+}
+
+#[tokio::test]
+async fn paused_focus_fetches_nothing() {
+    let (_focus_tx, focus_rx) = watch::channel(Focus {
+        paused: true,
+        resync: 1,
+        ..Focus::default()
+    });
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    poll::spawn(Arc::new(fixture()), focus_rx, tx, Settings::default());
+
+    assert!(drain(&mut rx, Duration::from_millis(200)).await.is_empty());
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn selection_change_cancels_in_flight_fetch() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(mock::serve(
+        listener,
+        Scenario {
+            latency_ms: 300,
+            ..Scenario::default()
+        },
+    ));
+    let src = Source::new(ApiClient::new(format!("http://{}", addr)).expect("client"));
+
+    let (focus_tx, focus_rx) = watch::channel(Focus {
+        live: true,
+        spawn_id: Some("old".to_string()),
+        ..Focus::default()
+    });
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    poll::spawn(Arc::new(src), focus_rx, tx, Settings::default());
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    focus_tx.send_modify(|focus| focus.spawn_id = Some("new".to_string()));
+
+    let records = drain(&mut rx, Duration::from_millis(700)).await;
+    let spawn_ids: Vec<&str> = records
+        .iter()
+        .filter_map(|r| match r {
+            Record::SpawnEvents { spawn_id, .. } => Some(spawn_id.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(spawn_ids, ["new"]);
+}
+
+#[tokio::test]
+async fn focus_change_fetches_new_scope_while_live() {
+    let (focus_tx, focus_rx) = watch::channel(Focus {
+        live: true,
+        resync: 1,
+        activity: Some(LedgerQuery::new(500).agent("a1b2c3d4-0000")),
+        tail: Some(Some("zealot".to_string())),
+        ..Focus::default()
+    });
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    poll::spawn(Arc::new(fixture()), focus_rx, tx, Settings::default());
+    drain(&mut rx, Duration::from_millis(200)).await;
+
+    // Live and not resynced: only the WS would have refreshed these before.
+    focus_tx.send_modify(|focus| {
+        focus.activity = Some(LedgerQuery::new(500).agent("h0000000-0000"));
+        focus.tail = Some(Some("tyson".to_string()));
+        focus.ledger = Some(LedgerQuery::new(500));
+    });
+    let records = drain(&mut rx, Duration::from_millis(200)).await;
+    let has = |pred: fn(&Record) -> bool| records.iter().any(pred);
+    assert!(has(|r| matches!(
+        r,
+        Record::Activity { agent_id: Some(id), delta: false, .. } if id == "h0000000-0000"
+    )));
+    assert!(has(
+        |r| matches!(r, Record::Tail { agent: Some(a), .. } if a == "tyson")
+    ));
+    assert!(has(|r| matches!(r, Record::Ledger { delta: false, .. })));
+    // The roster's scope didn't change, so the WS still covers it.
+    assert!(!has(|r| matches!(r, Record::Agents { .. })));
+}
+
+#[tokio::test]
+async fn daemon_check_refetches_daemon_while_live() {
+    let (focus_tx, focus_rx) = watch::channel(Focus {
+        live: true,
+        resync: 1,
+        ..Focus::default()
+    });
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    poll::spawn(Arc::new(fixture()), focus_rx, tx, Settings::default());
+    drain(&mut rx, Duration::from_millis(200)).await;
+
+    focus_tx.send_modify(|focus| focus.daemon_check += 1);
+    let records = drain(&mut rx, Duration::from_millis(200)).await;
+    assert!(records.iter().any(|r| matches!(r, Record::Daemon { .. })));
+    assert!(!records.iter().any(|r| matches!(r, Record::Agents { .. })));
+}
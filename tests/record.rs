@@ -0,0 +1,114 @@
+use chrono::{Duration as ChronoDuration, Utc};
+use space_cmd::app::AppState;
+use space_cmd::live::LiveEvent;
+use space_cmd::record::{self, Entry, Record, Recorder, Replay};
+use space_cmd::schema::{Activity, DaemonStatus};
+use space_cmd::source::{FixtureSource, Source};
+use std::time::Duration;
+
+fn ledger_item(id: &str) -> Activity {
+    Activity {
+        id: None,
+        agent_id: "a1".to_string(),
+        spawn_id: None,
+        primitive: "decision".to_string(),
+        primitive_id: id.to_string(),
+        action: "created".to_string(),
+        field: None,
+        after: None,
+        created_at: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+fn entries() -> Vec<Entry> {
+    let t0 = Utc::now();
+    let at = |s: i64| t0 + ChronoDuration::seconds(s);
+    vec![
+        Entry {
+            at: at(0),
+            record: Record::Ledger {
+                delta: false,
+                items: vec![ledger_item("l-1")],
+            },
+        },
+        Entry {
+            at: at(30),
+            record: Record::Live {
+                event: Box::new(LiveEvent::Ledger(ledger_item("l-2"))),
+            },
+        },
+        Entry {
+            at: at(60),
+            record: Record::Daemon {
+                status: DaemonStatus {
+                    concurrency: 4,
+                    ..DaemonStatus::default()
+                },
+            },
+        },
+    ]
+}
+
+#[tokio::test]
+async fn recorder_captures_source_responses() {
+    let path = std::env::temp_dir().join(format!("space-cmd-record-{}.jsonl", std::process::id()));
+    let fixture =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/swarm.json");
+    let src = Source::new(FixtureSource::load(&fixture).expect("fixture"))
+        .with_recorder(Recorder::create(&path).expect("recorder"));
+
+    src.get_agents().await.expect("agents");
+    src.get_agent_tail("zealot", 10).await.expect("tail");
+    src.record(|| Record::Live {
+        event: Box::new(LiveEvent::Daemon(DaemonStatus::default())),
+    });
+    // Dropping the source drains the recorder's writer.
+    drop(src);
+
+    let loaded = record::load(&path).expect("load log");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded.len(), 3);
+    assert!(matches!(&loaded[0].record, Record::Agents { agents } if agents.len() == 2));
+    assert!(
+        matches!(&loaded[1].record, Record::Tail { agent: Some(a), entries } if a == "zealot" && entries.len() == 2)
+    );
+    assert!(matches!(loaded[2].record, Record::Live { .. }));
+}
+
+#[test]
+fn replay_plays_forward_at_speed() {
+    let mut state = AppState::new();
+    let mut replay = Replay::new(entries()).expect("replay");
+
+    replay.advance(Duration::ZERO, &mut state);
+    assert_eq!(state.ledger.len(), 1);
+
+    replay.faster();
+    replay.advance(Duration::from_secs(20), &mut state);
+    assert_eq!(state.ledger.len(), 2);
+    assert_eq!(state.ledger[0].primitive_id, "l-2");
+    assert_eq!(state.daemon.concurrency, 0);
+
+    replay.advance(Duration::from_secs(60), &mut state);
+    assert_eq!(state.daemon.concurrency, 4);
+    assert!(!state.replay.expect("status").playing);
+}
+
+#[test]
+fn seeking_back_rebuilds_state() {
+    let mut state = AppState::new();
+    let mut replay = Replay::new(entries()).expect("replay");
+
+    replay.seek(120, &mut state);
+    assert_eq!(state.ledger.len(), 2);
+    assert_eq!(state.daemon.concurrency, 4);
+
+    replay.seek(-45, &mut state);
+    assert_eq!(state.ledger.len(), 1);
+    assert_eq!(state.daemon.concurrency, 0);
+}
+
+#[test]
+fn empty_log_has_nothing_to_replay() {
+    assert!(Replay::new(Vec::new()).is_none());
+}
@@ -0,0 +1,191 @@
+use space_cmd::app::{AppState, Hit, RightPane};
+use space_cmd::schema::{Activity, TailEntry};
+use space_cmd::search::{Pane, Query, Row};
+
+fn activity(agent_id: &str, primitive: &str, after: &str) -> Activity {
+    Activity {
+        id: None,
+        agent_id: agent_id.to_string(),
+        spawn_id: Some("s1abcdef".to_string()),
+        primitive: primitive.to_string(),
+        primitive_id: "p1".to_string(),
+        action: "created".to_string(),
+        field: None,
+        after: Some(after.to_string()),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+    }
+}
+
+fn tool(agent: &str, name: &str, args: &str) -> TailEntry {
+    TailEntry {
+        spawn: "s2abcdef".to_string(),
+        agent: agent.to_string(),
+        entry_type: "tool".to_string(),
+        content: None,
+        name: Some(name.to_string()),
+        args: Some(args.to_string()),
+        ctx_pct: None,
+    }
+}
+
+fn row<'a>(agent: &'a str, kind: &'a str, text: &'a str) -> Row<'a> {
+    Row {
+        agent,
+        kind,
+        text: vec![text],
+        ..Row::default()
+    }
+}
+
+fn hit(pane: Pane, index: usize) -> Hit {
+    Hit { pane, index }
+}
+
+fn searched(state: &mut AppState, query: &str) {
+    state.global_search_start();
+    query.chars().for_each(|ch| state.global_search_push(ch));
+    state.global_search_commit();
+}
+
+fn sample() -> AppState {
+    let mut state = AppState::new();
+    state
+        .agent_identities
+        .insert("a1".to_string(), "sentinel".to_string());
+    state
+        .agent_identities
+        .insert("a2".to_string(), "zealot".to_string());
+    state.activity = vec![
+        activity("a1", "task", "fix the flaky test"),
+        activity("a2", "task", "rewrite parser"),
+        activity("a1", "insight", "tests flake under load"),
+    ];
+    state.stream = vec![
+        tool("sentinel", "Bash", "cargo test"),
+        tool("zealot", "Read", "src/parser.rs"),
+    ];
+    state.ledger = vec![activity("a2", "decision", "ship the parser")];
+    state
+}
+
+#[test]
+fn parse_splits_field_filters_from_text() {
+    let query = Query::parse("agent:@Sentinel type:tool name:Bash cargo  test in:stream");
+    assert_eq!(query.agent.as_deref(), Some("sentinel"));
+    assert_eq!(query.kind.as_deref(), Some("tool"));
+    assert_eq!(query.name.as_deref(), Some("bash"));
+    assert_eq!(query.pane, Some(Pane::Stream));
+    assert_eq!(
+        query.text.map(|re| re.as_str().to_string()).as_deref(),
+        Some("cargo test")
+    );
+
+    let unknown = Query::parse("http://host in:nowhere");
+    assert!(unknown.pane.is_none());
+    assert!(unknown.agent.is_none());
+    assert!(
+        unknown
+            .text
+            .is_some_and(|re| re.is_match("GET http://host in:nowhere"))
+    );
+}
+
+#[test]
+fn text_is_a_smart_case_regex_with_literal_fallback() {
+    let query = Query::parse("flak(y|e)");
+    assert!(query.matches(Pane::Activity, &row("a", "task", "Flaky build")));
+    assert!(!query.matches(Pane::Activity, &row("a", "task", "flag")));
+
+    let sensitive = Query::parse("Parser");
+    assert!(sensitive.matches(Pane::Activity, &row("a", "task", "Parser rewrite")));
+    assert!(!sensitive.matches(Pane::Activity, &row("a", "task", "parser rewrite")));
+
+    let broken = Query::parse("foo(");
+    assert!(broken.matches(Pane::Activity, &row("a", "task", "call foo(1)")));
+}
+
+#[test]
+fn filters_must_all_match_and_empty_query_matches_nothing() {
+    let query = Query::parse("agent:sent type:tool");
+    assert!(query.matches(Pane::Stream, &row("sentinel", "tool", "x")));
+    assert!(!query.matches(Pane::Stream, &row("sentinel", "text", "x")));
+    assert!(!query.matches(Pane::Stream, &row("zealot", "tool", "x")));
+
+    let named = Query::parse("name:bash");
+    assert!(!named.matches(Pane::Stream, &row("sentinel", "tool", "x")));
+
+    let spawn = Query::parse("spawn:#s1a");
+    let in_spawn = Row {
+        spawn: Some("s1abcdef"),
+        ..row("sentinel", "task", "x")
+    };
+    assert!(spawn.matches(Pane::Activity, &in_spawn));
+    assert!(!Query::parse("spawn:abc").matches(Pane::Activity, &in_spawn));
+
+    assert!(!Query::parse("  ").matches(Pane::Activity, &row("a", "task", "x")));
+}
+
+#[test]
+fn hits_span_panes_and_filters_resolve_agent_identities() {
+    let mut state = sample();
+    searched(&mut state, "parser");
+    assert_eq!(
+        state.global_search_hits(),
+        [
+            hit(Pane::Activity, 1),
+            hit(Pane::Stream, 1),
+            hit(Pane::Ledger, 0),
+        ]
+    );
+
+    searched(&mut state, "agent:sentinel test");
+    let hits = state.global_search_hits();
+    assert_eq!(hits.len(), 3);
+    assert!(state.is_search_hit(Pane::Activity, 2));
+    assert!(state.is_search_hit(Pane::Stream, 0));
+    assert!(!state.is_search_hit(Pane::Activity, 1));
+
+    searched(&mut state, "type:tool name:Bash");
+    assert_eq!(state.global_search_hits(), [hit(Pane::Stream, 0)]);
+}
+
+#[test]
+fn next_and_prev_wrap_and_scroll_to_the_hit() {
+    let mut state = sample();
+    state.right_pane = RightPane::SpawnDetail;
+    searched(&mut state, "parser");
+    let current = |state: &AppState| state.global_search.as_ref().and_then(|s| s.current);
+
+    assert_eq!(current(&state), Some(hit(Pane::Activity, 1)));
+    assert_eq!(state.activity_scroll_offset, 1);
+    assert!(!state.global_search.as_ref().unwrap().editing);
+
+    state.global_search_next();
+    assert_eq!(state.right_pane, RightPane::Stream);
+    assert_eq!(state.stream_scroll_offset, 1);
+    assert!(state.is_current_hit(Pane::Stream, 1));
+
+    state.global_search_next();
+    assert_eq!(state.right_pane, RightPane::Ledger);
+    state.global_search_next();
+    assert_eq!(current(&state), Some(hit(Pane::Activity, 1)));
+
+    state.global_search_prev();
+    assert_eq!(current(&state), Some(hit(Pane::Ledger, 0)));
+}
+
+#[test]
+fn spawn_events_are_searched_and_empty_submit_closes() {
+    let mut state = sample();
+    state.spawn_activity = vec![activity("a1", "tool_use", "grep -rn unwrap src")];
+    searched(&mut state, "in:spawn type:tool unwrap");
+    assert_eq!(state.global_search_hits(), [hit(Pane::Spawn, 0)]);
+    assert_eq!(state.right_pane, RightPane::SpawnDetail);
+
+    state.global_search_cancel();
+    assert!(state.global_search.is_none());
+    assert!(!state.is_search_hit(Pane::Spawn, 0));
+
+    searched(&mut state, "");
+    assert!(state.global_search.is_none());
+}
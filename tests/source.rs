@@ -0,0 +1,114 @@
+use space_cmd::api::ApiError;
+use space_cmd::app::AppState;
+use space_cmd::health::{RepoHealthOptions, calculate_health};
+use space_cmd::schema::LedgerQuery;
+use space_cmd::source::{Endpoint, EndpointStats, FixtureSource, Source};
+
+fn stats(endpoint: Endpoint, failures: u32) -> EndpointStats {
+    EndpointStats {
+        endpoint,
+        last_success: None,
+        last_error: (failures > 0).then(|| ApiError::Network("connection refused".to_string())),
+        latency_ms: Some(12),
+        consecutive_failures: failures,
+        rejected: Vec::new(),
+    }
+}
+
+#[test]
+fn failing_endpoint_marks_its_panes_stale() {
+    let mut state = AppState::new();
+    state.endpoints = vec![stats(Endpoint::Agents, 0), stats(Endpoint::Ledger, 2)];
+
+    assert!(state.is_stale(Endpoint::Ledger));
+    assert!(!state.is_stale(Endpoint::Agents));
+    assert!(!state.is_stale(Endpoint::Tail));
+    assert!(!state.api_down());
+}
+
+#[test]
+fn worst_endpoint_has_most_consecutive_failures() {
+    let mut state = AppState::new();
+    state.endpoints = vec![
+        stats(Endpoint::Agents, 1),
+        stats(Endpoint::Spawns, 4),
+        stats(Endpoint::Tail, 0),
+    ];
+
+    let worst = state.worst_endpoint().expect("worst endpoint");
+    assert_eq!(worst.endpoint, Endpoint::Spawns);
+    assert_eq!(worst.consecutive_failures, 4);
+}
+
+#[test]
+fn api_down_only_when_every_endpoint_fails() {
+    let mut state = AppState::new();
+    assert!(!state.api_down());
+    assert!(state.worst_endpoint().is_none());
+
+    state.endpoints = vec![stats(Endpoint::Agents, 3), stats(Endpoint::Spawns, 1)];
+    assert!(state.api_down());
+}
+
+fn fixture_source() -> Source<FixtureSource> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/swarm.json");
+    Source::new(FixtureSource::load(&path).expect("load fixture"))
+}
+
+#[tokio::test]
+async fn fixture_backend_serves_snapshot_and_tracks_endpoints() {
+    let src = fixture_source();
+
+    let agents = src.get_agents().await.expect("agents");
+    assert_eq!(agents.len(), 2);
+    let activity = src
+        .get_activity(&LedgerQuery::new(10).agent("h0000000-0000"))
+        .await
+        .expect("activity");
+    assert_eq!(activity.len(), 1);
+    assert_eq!(activity[0].primitive, "task");
+    let tail = src.get_agent_tail("zealot", 1).await.expect("tail");
+    assert_eq!(tail[0].entry_type, "tool_call");
+    let events = src
+        .get_spawn_activity("s1s1s1s1-0000", 200)
+        .await
+        .expect("events");
+    assert_eq!(events.len(), 1);
+
+    let stats = src.endpoint_stats();
+    let endpoints: Vec<Endpoint> = stats.iter().map(|s| s.endpoint).collect();
+    assert_eq!(
+        endpoints,
+        vec![
+            Endpoint::Agents,
+            Endpoint::Ledger,
+            Endpoint::SpawnEvents,
+            Endpoint::Tail
+        ]
+    );
+    assert!(
+        stats
+            .iter()
+            .all(|s| s.last_success.is_some() && !s.is_failing())
+    );
+    assert!(src.has_fetched());
+}
+
+#[tokio::test]
+async fn health_scores_api_through_data_source() {
+    let src = fixture_source();
+    let empty = std::env::temp_dir().join(format!("space-cmd-health-{}", std::process::id()));
+    std::fs::create_dir_all(&empty).expect("temp dir");
+
+    let options = RepoHealthOptions {
+        repos: vec![empty.clone()],
+        ..RepoHealthOptions::default()
+    };
+    let result = calculate_health(src.backend(), options).await;
+    let _ = std::fs::remove_dir_all(&empty);
+
+    assert!(result.api_ok);
+    assert!(result.api_base_url.starts_with("fixture:"));
+    assert!(result.ledger_freshness_s.is_some());
+    assert!(result.spawns_freshness_s.is_some());
+}
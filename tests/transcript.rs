@@ -0,0 +1,144 @@
+use serde_json::json;
+use space_cmd::app::AppState;
+use space_cmd::schema::{Activity, Spawn};
+use space_cmd::wire::SpawnEventPayload;
+
+fn spawn(id: &str) -> Spawn {
+    Spawn {
+        id: id.to_string(),
+        agent_id: "a1".to_string(),
+        status: "active".to_string(),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+fn event(id: i64, kind: &str, body: &str) -> Activity {
+    Activity {
+        id: Some(id),
+        agent_id: "a1".to_string(),
+        spawn_id: Some("s1".to_string()),
+        primitive: kind.to_string(),
+        primitive_id: "s1".to_string(),
+        action: kind.to_string(),
+        field: None,
+        after: Some(body.to_string()),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+    }
+}
+
+fn opened() -> AppState {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("s1")];
+    state.spawn_activity = vec![
+        event(10, "text", "looking at the build"),
+        event(11, "tool_call", "cargo test --workspace"),
+        event(12, "tool_result", "test result: ok"),
+        event(13, "text", "build is green"),
+    ];
+    state.selected_spawn_idx = Some(0);
+    assert!(state.open_transcript());
+    state
+}
+
+#[test]
+fn open_uses_focused_spawn_when_none_selected() {
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("s1"), spawn("s2")];
+    state.active_spawn_idx = 1;
+
+    assert!(state.open_transcript());
+    assert_eq!(state.selected_spawn_idx, Some(1));
+    assert_eq!(state.transcript.as_ref().unwrap().spawn_id, "s2");
+}
+
+#[test]
+fn open_without_spawns_fails() {
+    let mut state = AppState::new();
+    assert!(!state.open_transcript());
+    assert!(state.transcript.is_none());
+}
+
+#[test]
+fn fold_only_toggles_tool_events() {
+    let mut state = opened();
+    state.transcript_toggle_fold();
+    assert!(state.transcript.as_ref().unwrap().expanded.is_empty());
+
+    state.transcript_next_event();
+    state.transcript_toggle_fold();
+    assert!(state.transcript.as_ref().unwrap().expanded.contains(&11));
+    state.transcript_toggle_fold();
+    assert!(state.transcript.as_ref().unwrap().expanded.is_empty());
+}
+
+#[test]
+fn repeated_events_fold_independently_and_keep_folds_as_events_arrive() {
+    let fetch = |kinds: &[&str]| -> Vec<Activity> {
+        kinds
+            .iter()
+            .enumerate()
+            .map(|(i, kind)| {
+                let raw = json!({
+                    "type": kind,
+                    "timestamp": "2026-02-05T10:00:00Z",
+                    "content": "cargo test",
+                });
+                serde_json::from_value::<SpawnEventPayload>(raw)
+                    .unwrap()
+                    .into_activity(i, "s1")
+            })
+            .collect()
+    };
+    let mut state = opened();
+    state.spawn_activity = fetch(&["text", "tool_call", "tool_call"]);
+    state.transcript_next_event();
+    state.transcript_toggle_fold();
+
+    state.spawn_activity = fetch(&["text", "tool_call", "tool_call", "result"]);
+    let view = state.transcript.as_ref().unwrap();
+    let expanded: Vec<bool> = state
+        .spawn_activity
+        .iter()
+        .map(|a| a.id.is_some_and(|id| view.expanded.contains(&id)))
+        .collect();
+    assert_eq!(expanded, [false, true, false, false]);
+}
+
+#[test]
+fn search_jumps_between_matches_and_expands_tools() {
+    let mut state = opened();
+    state.transcript_search_start();
+    for ch in "build".chars() {
+        state.transcript_search_push(ch);
+    }
+    state.transcript_search_commit();
+    assert_eq!(state.transcript_matches(), vec![0, 3]);
+    assert_eq!(state.transcript.as_ref().unwrap().cursor, 0);
+
+    state.transcript_next_match();
+    assert_eq!(state.transcript.as_ref().unwrap().cursor, 3);
+    state.transcript_next_match();
+    assert_eq!(state.transcript.as_ref().unwrap().cursor, 0);
+    state.transcript_prev_match();
+    assert_eq!(state.transcript.as_ref().unwrap().cursor, 3);
+
+    state.transcript_search_start();
+    for ch in "cargo".chars() {
+        state.transcript_search_push(ch);
+    }
+    state.transcript_search_commit();
+    let view = state.transcript.as_ref().unwrap();
+    assert_eq!(view.cursor, 1);
+    assert!(view.expanded.contains(&11));
+}
+
+#[test]
+fn event_navigation_is_bounded() {
+    let mut state = opened();
+    state.transcript_prev_event();
+    assert_eq!(state.transcript.as_ref().unwrap().cursor, 0);
+    state.transcript_last();
+    state.transcript_next_event();
+    assert_eq!(state.transcript.as_ref().unwrap().cursor, 3);
+}
@@ -0,0 +1,153 @@
+use space_cmd::app::{AppState, SpawnRow};
+use space_cmd::schema::Spawn;
+
+fn spawn(id: &str, caller: Option<&str>, status: &str, error: Option<&str>) -> Spawn {
+    Spawn {
+        id: id.to_string(),
+        agent_id: "a1".to_string(),
+        caller_spawn_id: caller.map(String::from),
+        status: status.to_string(),
+        error: error.map(String::from),
+        created_at: "2026-02-05T10:00:00Z".to_string(),
+        last_active_at: Some("2026-02-05T10:05:00Z".to_string()),
+        ..Default::default()
+    }
+}
+
+fn tree_state() -> AppState {
+    let mut state = AppState::new();
+    state.spawns = vec![
+        spawn("child-b", Some("root"), "done", Some("boom")),
+        spawn("root", None, "done", None),
+        spawn("grandchild", Some("child-a"), "done", None),
+        spawn("child-a", Some("root"), "done", None),
+        spawn("orphan", Some("missing"), "active", None),
+    ];
+    state.spawn_tree = true;
+    state
+}
+
+fn ids(state: &AppState, rows: &[SpawnRow]) -> Vec<(String, usize)> {
+    rows.iter()
+        .map(|r| (state.spawns[r.idx].id.clone(), r.depth))
+        .collect()
+}
+
+#[test]
+fn children_nest_under_caller() {
+    let state = tree_state();
+    let rows = state.visible_spawn_rows();
+    assert_eq!(
+        ids(&state, &rows),
+        vec![
+            ("root".to_string(), 0),
+            ("child-b".to_string(), 1),
+            ("child-a".to_string(), 1),
+            ("grandchild".to_string(), 2),
+            ("orphan".to_string(), 0),
+        ]
+    );
+    assert_eq!(rows[0].descendants, 3);
+}
+
+#[test]
+fn collapse_hides_descendants() {
+    let mut state = tree_state();
+    state.active_spawn_idx = 1;
+    state.toggle_spawn_collapse();
+
+    let rows = state.visible_spawn_rows();
+    assert_eq!(
+        ids(&state, &rows),
+        vec![("root".to_string(), 0), ("orphan".to_string(), 0)]
+    );
+
+    state.toggle_spawn_collapse();
+    assert_eq!(state.visible_spawn_rows().len(), 5);
+}
+
+fn row(state: &AppState, id: &str) -> SpawnRow {
+    state
+        .visible_spawn_rows()
+        .into_iter()
+        .find(|r| state.spawns[r.idx].id == id)
+        .expect("row")
+}
+
+#[test]
+fn failure_propagates_to_ancestors() {
+    let state = tree_state();
+    assert!(row(&state, "root").failed);
+    assert!(!row(&state, "child-a").failed);
+    assert!(row(&state, "orphan").active);
+    assert!(!row(&state, "root").active);
+}
+
+#[test]
+fn navigation_follows_tree_order() {
+    let mut state = tree_state();
+    state.active_spawn_idx = 1;
+    state.next_in_sidebar();
+    assert_eq!(state.spawns[state.active_spawn_idx].id, "child-b");
+    state.next_in_sidebar();
+    assert_eq!(state.spawns[state.active_spawn_idx].id, "child-a");
+    state.prev_in_sidebar();
+    state.prev_in_sidebar();
+    state.prev_in_sidebar();
+    assert_eq!(state.spawns[state.active_spawn_idx].id, "orphan");
+}
+
+#[test]
+fn subtree_bounds_span_descendants() {
+    let mut state = tree_state();
+    state.spawns[2].last_active_at = Some("2026-02-05T11:00:00Z".to_string());
+    let root = row(&state, "root");
+    assert_eq!(root.start, "2026-02-05T10:00:00Z");
+    assert_eq!(root.end.as_deref(), Some("2026-02-05T11:00:00Z"));
+
+    state.spawns[2].status = "active".to_string();
+    assert_eq!(row(&state, "root").end, None);
+    assert_eq!(
+        row(&state, "child-b").end.as_deref(),
+        Some("2026-02-05T10:05:00Z")
+    );
+}
+
+#[test]
+fn caller_cycles_still_show_in_tree_mode() {
+    let mut state = AppState::new();
+    state.spawns = vec![
+        spawn("root", None, "done", None),
+        spawn("loop-a", Some("loop-b"), "done", None),
+        spawn("loop-b", Some("loop-a"), "active", None),
+        spawn("leaf", Some("loop-b"), "done", None),
+    ];
+    state.spawn_tree = true;
+
+    let rows = state.visible_spawn_rows();
+    assert_eq!(
+        ids(&state, &rows),
+        vec![
+            ("root".to_string(), 0),
+            ("loop-a".to_string(), 0),
+            ("loop-b".to_string(), 1),
+            ("leaf".to_string(), 2),
+        ]
+    );
+    assert_eq!(rows[1].descendants, 2);
+    assert!(rows[1].active);
+}
+
+#[test]
+fn failed_status_shows_as_failed_on_the_node_itself() {
+    let failed = spawn("child", Some("root"), "failed", None);
+    assert!(failed.is_failed());
+    assert_eq!(failed.status_icon(), "x");
+
+    let mut state = AppState::new();
+    state.spawns = vec![spawn("root", None, "done", None), failed];
+    state.spawn_tree = true;
+    let rows = state.visible_spawn_rows();
+    assert!(rows.iter().all(|r| r.failed));
+    assert_eq!(spawn("queued", None, "pending", None).status_icon(), "?");
+}